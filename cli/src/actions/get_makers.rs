@@ -1,4 +1,4 @@
-use airswap::{Config as AirswapConfig, RegistryClient};
+use airswap::{BatchOptions, MakerWithSupportedTokens, RegistryClient};
 use alloy::providers::{Provider, ProviderBuilder};
 use anyhow::Result;
 use cli_table::{
@@ -28,14 +28,22 @@ impl Action for GetMakersAction {
         let provider = ProviderBuilder::new().on_http(self.config.rpc.parse()?);
         let provider = Arc::new(provider);
         let chain_id = provider.get_chain_id().await?.to_u64().unwrap();
-        let config = AirswapConfig::new(chain_id, self.config.protocol_version);
+        let config = self.config.airswap_config(&provider, chain_id).await?;
         let registry = RegistryClient::new(provider, config);
 
         let makers = registry
-            .get_makers_with_supported_tokens()
+            .get_makers_with_supported_tokens_batch(&BatchOptions::default())
             .await?
+            .results
             .into_iter()
-            .map(Maker::from)
+            .map(|(maker, tokens)| match tokens {
+                Ok(tokens) => Maker::from(MakerWithSupportedTokens::new(maker, tokens)),
+                Err(_) => Maker {
+                    address: format!("{:?}", maker.address),
+                    url: maker.url,
+                    status: MakerStatus::Unknown,
+                },
+            })
             .collect::<Vec<_>>();
 
         let table = makers
@@ -60,6 +68,7 @@ pub struct Maker {
 pub enum MakerStatus {
     Active,
     Inactive,
+    Unknown,
 }
 
 impl Display for MakerStatus {
@@ -67,6 +76,7 @@ impl Display for MakerStatus {
         match self {
             MakerStatus::Active => write!(f, "Active"),
             MakerStatus::Inactive => write!(f, "Inactive"),
+            MakerStatus::Unknown => write!(f, "Unknown"),
         }
     }
 }
@@ -80,8 +90,8 @@ impl From<usize> for MakerStatus {
     }
 }
 
-impl From<airswap::MakerWithSupportedTokens> for Maker {
-    fn from(value: airswap::MakerWithSupportedTokens) -> Self {
+impl From<MakerWithSupportedTokens> for Maker {
+    fn from(value: MakerWithSupportedTokens) -> Self {
         Self {
             address: format!("{:?}", value.maker.address),
             url: value.maker.url,
@@ -1,4 +1,4 @@
-use airswap::{Config as AirswapConfig, RegistryClient};
+use airswap::RegistryClient;
 use alloy::providers::{Provider, ProviderBuilder};
 use alloy_erc20::{BasicTokenStore, Erc20ProviderExt};
 use anyhow::Result;
@@ -31,7 +31,7 @@ impl Action for GetTokensAction {
     async fn execute(&self) -> Result<()> {
         let provider = ProviderBuilder::new().on_http(self.config.rpc.parse()?);
         let chain_id = provider.get_chain_id().await?.to_u64().unwrap();
-        let config = AirswapConfig::new(chain_id, self.config.protocol_version);
+        let config = self.config.airswap_config(&provider, chain_id).await?;
         let registry_client = RegistryClient::new(provider.clone(), config);
 
         let mut token_store = BasicTokenStore::new();
@@ -1,4 +1,4 @@
-use airswap::{Config as AirswapConfig, MakerClient, MakerWithSupportedTokens, RegistryClient};
+use airswap::{BatchOptions, MakerClient, MakerWithSupportedTokens, RegistryClient};
 use alloy::primitives::{utils::parse_units, Address};
 use alloy::providers::{Provider, ProviderBuilder};
 use alloy_erc20::{BasicTokenStore, TokenId, TokenStore};
@@ -51,7 +51,7 @@ impl Action for QuoteAction {
         let provider = ProviderBuilder::new().on_http(self.config.rpc.parse()?);
         let provider = Arc::new(provider);
         let chain_id = provider.get_chain_id().await?.to_u64().unwrap();
-        let config = AirswapConfig::new(chain_id, self.config.protocol_version);
+        let config = self.config.airswap_config(&provider, chain_id).await?;
         let registry_client = RegistryClient::new(provider.clone(), config.clone());
 
         let mut store = BasicTokenStore::new();
@@ -67,9 +67,22 @@ impl Action for QuoteAction {
         let to_token = store
             .get(chain_id, TokenId::Symbol(self.to_symbol.clone()))
             .ok_or(anyhow!("The token {} can't be found", &self.to_symbol))?;
-        let makers = registry_client
-            .get_makers_with_supported_tokens()
-            .await?
+        let batch = registry_client
+            .get_makers_with_supported_tokens_batch(&BatchOptions::default())
+            .await?;
+        // The makers whose tokens can't be fetched may support the pair
+        let failures = batch
+            .failures()
+            .filter(|(m, _)| self.maker.map_or(true, |address| address == m.address))
+            .map(|(m, err)| {
+                Quote::new(
+                    m.url.clone(),
+                    format!("Can't get the supported tokens: {err:#}"),
+                )
+            })
+            .collect::<Vec<_>>();
+        let makers = batch
+            .into_makers()
             .into_iter()
             .filter(|m: &MakerWithSupportedTokens| !m.supported_tokens.is_empty())
             .filter(|m| {
@@ -126,10 +139,11 @@ impl Action for QuoteAction {
             })
         });
 
-        let (quotes, _) = join_all(tasks)
+        let (mut quotes, _) = join_all(tasks)
             .await
             .into_iter()
             .partition_result::<Vec<_>, Vec<_>, _, _>();
+        quotes.extend(failures);
 
         let table = quotes
             .table()
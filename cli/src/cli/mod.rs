@@ -1,7 +1,8 @@
 use std::ops::Deref;
 
-use airswap::ProtocolVersion;
-use alloy::primitives::Address;
+use airswap::{detect_latest, Config as AirswapConfig, ProtocolVersion};
+use alloy::{network::Network, primitives::Address, providers::Provider, transports::Transport};
+use anyhow::Result;
 use clap::{Args, Parser, Subcommand};
 
 use crate::actions::{
@@ -67,13 +68,34 @@ pub struct SellCommand {
 
 #[derive(Args, Clone)]
 pub struct Config {
-    #[arg(short = 'v', long, default_value_t)]
-    pub protocol_version: ProtocolVersion,
+    /// Detected from the deployed contracts when not set
+    #[arg(short = 'v', long)]
+    pub protocol_version: Option<ProtocolVersion>,
 
     #[arg(long, env)]
     pub rpc: String,
 }
 
+impl Config {
+    pub async fn airswap_config<P, T, N>(
+        &self,
+        provider: &P,
+        chain_id: u64,
+    ) -> Result<AirswapConfig>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let config = match self.protocol_version {
+            Some(protocol_version) => AirswapConfig::try_new(chain_id, protocol_version)?,
+            None => detect_latest(provider).await?,
+        };
+
+        Ok(config)
+    }
+}
+
 pub struct BoxedAction(Box<dyn Action>);
 
 impl Deref for BoxedAction {
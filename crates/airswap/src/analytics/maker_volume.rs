@@ -0,0 +1,48 @@
+use std::{collections::HashMap, fmt::Display};
+
+use alloy::primitives::Address;
+
+use crate::{maker_info, Maker, SwapRecord};
+
+use super::{PairVolume, TokenPair};
+
+#[derive(Debug, Clone)]
+pub struct MakerVolume {
+    pub signer_wallet: Address,
+    pub maker: Option<Maker>,
+    pub trade_count: usize,
+    pub pairs: HashMap<TokenPair, PairVolume>,
+}
+
+impl MakerVolume {
+    pub fn new(signer_wallet: Address) -> Self {
+        Self {
+            signer_wallet,
+            maker: None,
+            trade_count: 0,
+            pairs: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, record: &SwapRecord) {
+        self.trade_count += 1;
+        self.pairs
+            .entry(TokenPair::from(record))
+            .or_default()
+            .add(record);
+    }
+
+    pub fn name(&self) -> String {
+        match (maker_info(&self.signer_wallet), &self.maker) {
+            (Some(info), _) => info.name,
+            (None, Some(maker)) if !maker.url.is_empty() => maker.url.clone(),
+            _ => format!("{}", self.signer_wallet),
+        }
+    }
+}
+
+impl Display for MakerVolume {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,9 @@
+mod maker_volume;
+mod pair_volume;
+mod swap_analytics;
+mod token_pair;
+
+pub use maker_volume::MakerVolume;
+pub use pair_volume::PairVolume;
+pub use swap_analytics::SwapAnalytics;
+pub use token_pair::TokenPair;
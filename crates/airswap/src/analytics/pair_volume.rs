@@ -0,0 +1,47 @@
+use alloy::primitives::U256;
+use bigdecimal::{num_bigint::BigInt, BigDecimal};
+
+use crate::SwapRecord;
+
+const FEE_DIVISOR: u64 = 10000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PairVolume {
+    pub trade_count: usize,
+    pub signer_amount: U256,
+    pub sender_amount: U256,
+    // Collected in the signer token
+    pub protocol_fees: U256,
+}
+
+impl PairVolume {
+    pub fn add(&mut self, record: &SwapRecord) {
+        let event = &record.event;
+
+        self.trade_count += 1;
+        self.signer_amount += event.signerAmount;
+        self.sender_amount += event.senderAmount;
+        self.protocol_fees += event.signerAmount * event.protocolFee / U256::from(FEE_DIVISOR);
+    }
+
+    /// Volume weighted price, in sender token per signer token, scaled with the token decimals.
+    pub fn effective_price(&self, signer_decimals: u8, sender_decimals: u8) -> Option<BigDecimal> {
+        if self.signer_amount.is_zero() {
+            return None;
+        }
+
+        let signer_amount = to_big_decimal(self.signer_amount, signer_decimals);
+        let sender_amount = to_big_decimal(self.sender_amount, sender_decimals);
+
+        Some(sender_amount / signer_amount)
+    }
+}
+
+pub(crate) fn to_big_decimal(amount: U256, decimals: u8) -> BigDecimal {
+    let digits = BigInt::from_bytes_be(
+        bigdecimal::num_bigint::Sign::Plus,
+        &amount.to_be_bytes::<32>(),
+    );
+
+    BigDecimal::new(digits, decimals as i64)
+}
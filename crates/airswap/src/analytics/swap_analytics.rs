@@ -0,0 +1,173 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use alloy::primitives::Address;
+
+use crate::{RegistryClient, RegistryError, SwapRecord};
+
+use super::{MakerVolume, PairVolume, TokenPair};
+
+#[derive(Debug, Clone)]
+pub struct SwapAnalytics {
+    bucket_size: u64,
+    makers: HashMap<Address, MakerVolume>,
+    pairs: HashMap<TokenPair, PairVolume>,
+    buckets: BTreeMap<u64, HashMap<TokenPair, PairVolume>>,
+}
+
+impl SwapAnalytics {
+    pub fn new(bucket_size: Duration) -> Self {
+        Self {
+            bucket_size: bucket_size.as_secs().max(1),
+            makers: HashMap::new(),
+            pairs: HashMap::new(),
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn daily() -> Self {
+        Self::new(Duration::from_secs(24 * 60 * 60))
+    }
+
+    pub fn from_records<'a, I>(bucket_size: Duration, records: I) -> Self
+    where
+        I: IntoIterator<Item = &'a SwapRecord>,
+    {
+        let mut analytics = Self::new(bucket_size);
+
+        for record in records {
+            analytics.add(record);
+        }
+
+        analytics
+    }
+
+    pub fn add(&mut self, record: &SwapRecord) {
+        let pair = TokenPair::from(record);
+        let signer_wallet = record.event.signerWallet;
+
+        self.makers
+            .entry(signer_wallet)
+            .or_insert_with(|| MakerVolume::new(signer_wallet))
+            .add(record);
+
+        self.pairs.entry(pair).or_default().add(record);
+
+        // Records without a timestamp can't be bucketed
+        if let Some(timestamp) = record.timestamp {
+            let bucket = timestamp - timestamp % self.bucket_size;
+
+            self.buckets
+                .entry(bucket)
+                .or_default()
+                .entry(pair)
+                .or_default()
+                .add(record);
+        }
+    }
+
+    /// Resolves the signer wallets to the makers registered in the registry.
+    pub async fn resolve_makers(&mut self, registry: &RegistryClient) -> Result<(), RegistryError> {
+        let makers = registry
+            .get_makers()
+            .await?
+            .into_iter()
+            .map(|m| (m.address, m))
+            .collect::<HashMap<_, _>>();
+
+        for (address, volume) in self.makers.iter_mut() {
+            volume.maker = makers.get(address).cloned();
+        }
+
+        Ok(())
+    }
+
+    pub fn maker(&self, signer_wallet: &Address) -> Option<&MakerVolume> {
+        self.makers.get(signer_wallet)
+    }
+
+    /// The makers, sorted by descending trade count.
+    pub fn makers(&self) -> Vec<&MakerVolume> {
+        let mut makers = self.makers.values().collect::<Vec<_>>();
+        makers.sort_by_key(|m| Reverse(m.trade_count));
+        makers
+    }
+
+    pub fn pair(&self, pair: &TokenPair) -> Option<&PairVolume> {
+        self.pairs.get(pair)
+    }
+
+    pub fn pairs(&self) -> &HashMap<TokenPair, PairVolume> {
+        &self.pairs
+    }
+
+    /// The volumes per pair, keyed by the timestamp of the start of each bucket.
+    pub fn buckets(&self) -> &BTreeMap<u64, HashMap<TokenPair, PairVolume>> {
+        &self.buckets
+    }
+
+    pub fn trade_count(&self) -> usize {
+        self.pairs.values().map(|p| p.trade_count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use alloy::primitives::{address, Address, B256, U256};
+
+    use crate::{SwapERC20Contract, SwapRecord};
+
+    use super::{SwapAnalytics, TokenPair};
+
+    const MAKER: Address = address!("111bb8c3542f2b92fb41b8d913c01d3788431111");
+    const USDC: Address = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+    const WETH: Address = address!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+
+    fn record(timestamp: u64, signer_amount: u64, sender_amount: u64) -> SwapRecord {
+        SwapRecord {
+            event: SwapERC20Contract::SwapERC20 {
+                nonce: U256::ZERO,
+                signerWallet: MAKER,
+                signerToken: WETH,
+                signerAmount: U256::from(signer_amount),
+                protocolFee: U256::from(5),
+                senderWallet: Address::ZERO,
+                senderToken: USDC,
+                senderAmount: U256::from(sender_amount),
+            },
+            address: Address::ZERO,
+            block_number: 0,
+            block_hash: B256::ZERO,
+            timestamp: Some(timestamp),
+            transaction_hash: B256::ZERO,
+            transaction_index: 0,
+            log_index: 0,
+        }
+    }
+
+    #[test]
+    fn aggregate() {
+        let records = [
+            record(10, 100000, 300000),
+            record(20, 200000, 600000),
+            record(100, 100000, 310000),
+        ];
+
+        let analytics = SwapAnalytics::from_records(Duration::from_secs(60), &records);
+        let pair = analytics.pair(&TokenPair::new(WETH, USDC)).unwrap();
+
+        assert_eq!(analytics.trade_count(), 3);
+        assert_eq!(analytics.maker(&MAKER).unwrap().name(), "B2C2");
+        assert_eq!(pair.protocol_fees, U256::from(200));
+        assert_eq!(pair.effective_price(0, 0).unwrap().to_string(), "3.025");
+        assert_eq!(
+            analytics.buckets().keys().copied().collect::<Vec<_>>(),
+            vec![0, 60]
+        );
+    }
+}
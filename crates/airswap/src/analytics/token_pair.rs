@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+use alloy::primitives::Address;
+
+use crate::SwapRecord;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TokenPair {
+    pub signer_token: Address,
+    pub sender_token: Address,
+}
+
+impl TokenPair {
+    pub fn new(signer_token: Address, sender_token: Address) -> Self {
+        Self {
+            signer_token,
+            sender_token,
+        }
+    }
+}
+
+impl From<&SwapRecord> for TokenPair {
+    fn from(value: &SwapRecord) -> Self {
+        Self::new(value.event.signerToken, value.event.senderToken)
+    }
+}
+
+impl Display for TokenPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.signer_token, self.sender_token)
+    }
+}
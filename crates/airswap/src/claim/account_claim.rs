@@ -0,0 +1,21 @@
+use alloy::primitives::{B256, U256};
+
+use crate::pool::Claim;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountClaim {
+    pub tree: B256,
+    pub root: B256,
+    pub value: U256,
+    pub proof: Vec<B256>,
+}
+
+impl From<AccountClaim> for Claim {
+    fn from(value: AccountClaim) -> Self {
+        Claim {
+            tree: value.tree,
+            value: value.value,
+            proof: value.proof,
+        }
+    }
+}
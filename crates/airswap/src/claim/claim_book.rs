@@ -0,0 +1,121 @@
+use std::{collections::HashMap, io::Read, path::Path};
+
+use alloy::primitives::{Address, B256};
+use itertools::Itertools;
+
+use super::{AccountClaim, ClaimError, GroupedProposal, MerkleTree, Proposals};
+
+#[derive(Debug, Clone)]
+pub struct ClaimBook {
+    trees: HashMap<B256, ClaimTree>,
+}
+
+#[derive(Debug, Clone)]
+struct ClaimTree {
+    proposal: GroupedProposal,
+    merkle_tree: MerkleTree,
+}
+
+impl ClaimBook {
+    pub fn new(proposals: Proposals) -> Self {
+        let trees = proposals
+            .into_iter()
+            .map(|(tree, proposal)| {
+                let merkle_tree = proposal.merkle_tree();
+
+                (
+                    tree,
+                    ClaimTree {
+                        proposal,
+                        merkle_tree,
+                    },
+                )
+            })
+            .collect();
+
+        Self { trees }
+    }
+
+    /// The proposals bundled with the crate.
+    pub fn embedded() -> Self {
+        Self::new(Proposals::embedded())
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ClaimError> {
+        Ok(Self::new(Proposals::from_reader(reader)?))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ClaimError> {
+        Ok(Self::new(Proposals::from_file(path)?))
+    }
+
+    pub fn trees(&self) -> impl Iterator<Item = &B256> {
+        self.trees.keys()
+    }
+
+    pub fn get_proposal(&self, tree: &B256) -> Option<&GroupedProposal> {
+        self.trees.get(tree).map(|t| &t.proposal)
+    }
+
+    pub fn get_claim(
+        &self,
+        account: Address,
+        tree: &B256,
+    ) -> Result<Option<AccountClaim>, ClaimError> {
+        let Some(claim_tree) = self.trees.get(tree) else {
+            return Ok(None);
+        };
+        let Some(vote) = claim_tree
+            .proposal
+            .votes
+            .iter()
+            .find(|v| v.address == account)
+        else {
+            return Ok(None);
+        };
+
+        let proof = claim_tree.merkle_tree.get_proof(&B256::from(vote))?;
+
+        Ok(Some(AccountClaim {
+            tree: *tree,
+            root: claim_tree.proposal.root,
+            value: vote.points,
+            proof,
+        }))
+    }
+
+    /// Every claim the account can make, ordered by tree.
+    pub fn get_claims(&self, account: Address) -> Result<Vec<AccountClaim>, ClaimError> {
+        let mut claims = vec![];
+
+        for tree in self.trees.keys().sorted() {
+            if let Some(claim) = self.get_claim(account, tree)? {
+                claims.push(claim);
+            }
+        }
+
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, b256, U256};
+
+    use super::ClaimBook;
+
+    #[test]
+    fn embedded_claims() {
+        let book = ClaimBook::embedded();
+        let tree = b256!("2321a10cf12b1095a6c22ac0ba0a150d2e08b60e0f1040e32ec247494ce76ac7");
+
+        let claim = book
+            .get_claim(address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff"), &tree)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(claim.root, book.get_proposal(&tree).unwrap().root);
+        assert_eq!(claim.value, U256::from(125770000));
+        assert!(!claim.proof.is_empty());
+    }
+}
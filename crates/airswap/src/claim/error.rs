@@ -0,0 +1,22 @@
+use alloy::primitives::{Address, B256};
+use thiserror::Error;
+
+use super::merkle::MerkleError;
+
+#[derive(Error, Debug)]
+pub enum ClaimError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Merkle(#[from] MerkleError),
+    #[error("The root of the tree {tree} is {root} but its votes give {computed}")]
+    RootMismatch {
+        tree: B256,
+        root: B256,
+        computed: B256,
+    },
+    #[error("{voter} voted more than once in the tree {tree}")]
+    DuplicateVoter { tree: B256, voter: Address },
+}
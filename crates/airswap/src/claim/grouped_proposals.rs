@@ -1,10 +1,27 @@
 use alloy::primitives::B256;
-use serde::Deserialize;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
-use super::vote::Vote;
+use super::{vote::Vote, MerkleTree};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupedProposal {
     pub root: B256,
     pub votes: Vec<Vote>,
 }
+
+impl GroupedProposal {
+    /// The tree of the votes, with the leaves sorted as the pool expects them.
+    pub fn merkle_tree(&self) -> MerkleTree {
+        MerkleTree::from_leaves(self.votes.iter().map(B256::from).sorted())
+    }
+
+    pub fn compute_root(&self) -> B256 {
+        self.merkle_tree().root()
+    }
+
+    /// Whether the stored root is the one of the votes.
+    pub fn is_root_valid(&self) -> bool {
+        self.compute_root() == self.root
+    }
+}
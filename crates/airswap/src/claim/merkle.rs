@@ -1,11 +1,20 @@
-use std::fmt::Debug;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+};
 
 use alloy::primitives::{keccak256, B256};
 use thiserror::Error;
 
+use super::MultiProof;
+
+const BYTES_VERSION: u8 = 1;
+
 #[derive(Clone)]
 pub struct MerkleTree {
     layers: Vec<Vec<B256>>,
+    indexes: HashMap<B256, usize>,
 }
 
 impl MerkleTree {
@@ -30,15 +39,33 @@ impl MerkleTree {
             layers.push(current_layer)
         }
 
-        Self { layers }
+        let mut indexes = HashMap::new();
+
+        for (i, leaf) in layers[0].iter().enumerate() {
+            indexes.entry(*leaf).or_insert(i);
+        }
+
+        Self { layers, indexes }
+    }
+
+    pub fn leaves(&self) -> &[B256] {
+        &self.layers[0]
+    }
+
+    pub fn index_of(&self, leaf: &B256) -> Option<usize> {
+        self.indexes.get(leaf).copied()
+    }
+
+    pub fn root(&self) -> B256 {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
     }
 
     pub fn get_proof(&self, leaf: &B256) -> Result<Vec<B256>, MerkleError> {
-        let mut index = self
-            .layers
-            .first()
-            .and_then(|leaves| leaves.iter().position(|l| l == leaf))
-            .ok_or(MerkleError::LeafNotFound)?;
+        let mut index = self.index_of(leaf).ok_or(MerkleError::LeafNotFound)?;
         let mut proofs = vec![];
 
         for layer in self.layers.iter() {
@@ -52,6 +79,214 @@ impl MerkleTree {
 
         Ok(proofs)
     }
+
+    /// Verifies a proof the same way OpenZeppelin's `MerkleProof.verify` does.
+    pub fn verify(leaf: &B256, proof: &[B256], root: &B256) -> bool {
+        let computed = proof
+            .iter()
+            .fold(*leaf, |computed, node| hash_pair(&computed, node));
+
+        computed == *root
+    }
+
+    /// Builds a proof of several leaves, verifiable with OpenZeppelin's
+    /// `MerkleProof.multiProofVerify`.
+    pub fn get_multi_proof(&self, leaves: &[B256]) -> Result<MultiProof, MerkleError> {
+        let mut indexes = leaves
+            .iter()
+            .map(|leaf| self.index_of(leaf).ok_or(MerkleError::LeafNotFound))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        indexes.sort();
+        indexes.dedup();
+
+        if indexes.is_empty() {
+            return Ok(MultiProof {
+                leaves: vec![],
+                proof: vec![self.root()],
+                proof_flags: vec![],
+            });
+        }
+
+        // The verifier reads the leaves, then the hashes it computes, from a single queue. Read
+        // backwards, this queue is a breadth first traversal of the computed nodes, starting
+        // from the root, which the verifier can follow only if every computed node comes before
+        // every leaf. Visiting the deepest subtrees first gives such a traversal whenever one
+        // exists, promoted nodes being seen at the height they are paired at.
+        let mut heights = HashMap::new();
+        let mut queue = VecDeque::from([self.paired_position(self.layers.len() - 1, 0)]);
+        let mut leaf_positions = vec![];
+        let mut proof = vec![];
+        let mut proof_flags = vec![];
+
+        while let Some((height, index)) = queue.pop_front() {
+            if height == 0 {
+                leaf_positions.push(index);
+                continue;
+            }
+
+            if !leaf_positions.is_empty() {
+                return Err(MerkleError::UnsupportedMultiProof);
+            }
+
+            let (mut known, unknown): (Vec<_>, Vec<_>) = self
+                .children(height, index)
+                .into_iter()
+                .partition(|&child| self.covers(child, &indexes));
+
+            known
+                .sort_by_key(|&child| Reverse(self.computed_height(child, &indexes, &mut heights)));
+
+            match unknown.first() {
+                Some(&(h, i)) => {
+                    proof.push(self.layers[h][i]);
+                    proof_flags.push(false);
+                }
+                None => proof_flags.push(true),
+            }
+
+            queue.extend(known);
+        }
+
+        proof.reverse();
+        proof_flags.reverse();
+
+        Ok(MultiProof {
+            leaves: leaf_positions
+                .into_iter()
+                .rev()
+                .map(|i| self.layers[0][i])
+                .collect(),
+            proof,
+            proof_flags,
+        })
+    }
+
+    // The position a node is hashed with its sibling at, nodes without sibling being the same
+    // as their only child
+    fn paired_position(&self, mut height: usize, mut index: usize) -> (usize, usize) {
+        while height > 0 && !is_tree_node(&self.layers[height - 1], right_child_index(index)) {
+            height -= 1;
+            index = left_child_index(index);
+        }
+
+        (height, index)
+    }
+
+    fn children(&self, height: usize, index: usize) -> [(usize, usize); 2] {
+        [
+            self.paired_position(height - 1, left_child_index(index)),
+            self.paired_position(height - 1, right_child_index(index)),
+        ]
+    }
+
+    // Whether one of the sorted leaf indexes is under the node
+    fn covers(&self, (height, index): (usize, usize), indexes: &[usize]) -> bool {
+        let first = index << height;
+        let end = (index + 1) << height;
+        let i = indexes.partition_point(|&leaf| leaf < first);
+
+        i < indexes.len() && indexes[i] < end
+    }
+
+    // How many hashes deep the verifier computes below the node, `None` for a leaf
+    fn computed_height(
+        &self,
+        (height, index): (usize, usize),
+        indexes: &[usize],
+        heights: &mut HashMap<(usize, usize), Option<usize>>,
+    ) -> Option<usize> {
+        if height == 0 {
+            return None;
+        }
+
+        if let Some(&computed) = heights.get(&(height, index)) {
+            return computed;
+        }
+
+        let computed = self
+            .children(height, index)
+            .into_iter()
+            .filter(|&child| self.covers(child, indexes))
+            .map(|child| {
+                self.computed_height(child, indexes, heights)
+                    .map_or(0, |h| h + 1)
+            })
+            .max();
+        heights.insert((height, index), computed);
+
+        computed
+    }
+
+    pub fn verify_multi_proof(multi_proof: &MultiProof, root: &B256) -> bool {
+        multi_proof.process() == Some(*root)
+    }
+
+    /// Serializes every layer, so the tree can be loaded back without hashing.
+    /// The format is a version byte, the leaf count as a big endian u64, then the nodes layer
+    /// by layer starting from the leaves.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let node_count = self.layers.iter().map(Vec::len).sum::<usize>();
+        let mut bytes = Vec::with_capacity(9 + node_count * 32);
+
+        bytes.push(BYTES_VERSION);
+        bytes.extend_from_slice(&(self.layers[0].len() as u64).to_be_bytes());
+
+        for node in self.layers.iter().flatten() {
+            bytes.extend_from_slice(node.as_slice());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleError> {
+        let (version, bytes) = bytes.split_first().ok_or(MerkleError::MalformedBytes)?;
+
+        if *version != BYTES_VERSION {
+            return Err(MerkleError::UnknownFormat(format!("binary v{version}")));
+        }
+
+        let (leaf_count, mut nodes) = bytes
+            .split_first_chunk::<8>()
+            .ok_or(MerkleError::MalformedBytes)?;
+        let leaf_count = usize::try_from(u64::from_be_bytes(*leaf_count))
+            .map_err(|_| MerkleError::MalformedBytes)?;
+
+        let mut layer_sizes = vec![leaf_count];
+        while let Some(&size) = layer_sizes.last().filter(|&&size| size > 1) {
+            layer_sizes.push(size.div_ceil(2));
+        }
+
+        let node_count = layer_sizes
+            .iter()
+            .try_fold(0_usize, |count, &size| count.checked_add(size))
+            .ok_or(MerkleError::MalformedBytes)?;
+
+        if node_count.checked_mul(32) != Some(nodes.len()) {
+            return Err(MerkleError::MalformedBytes);
+        }
+
+        let mut layers = vec![];
+
+        for size in layer_sizes {
+            let (layer, rest) = nodes.split_at(size * 32);
+            layers.push(
+                layer
+                    .chunks_exact(32)
+                    .map(B256::from_slice)
+                    .collect::<Vec<_>>(),
+            );
+            nodes = rest;
+        }
+
+        let mut indexes = HashMap::new();
+
+        for (i, leaf) in layers[0].iter().enumerate() {
+            indexes.entry(*leaf).or_insert(i);
+        }
+
+        Ok(Self { layers, indexes })
+    }
 }
 
 impl Debug for MerkleTree {
@@ -66,6 +301,18 @@ impl Debug for MerkleTree {
 pub enum MerkleError {
     #[error("The leaf don't exists in the tree")]
     LeafNotFound,
+    #[error("The leaves can't be proven together in this tree")]
+    UnsupportedMultiProof,
+    #[error("Unknown tree format: {0}")]
+    UnknownFormat(String),
+    #[error("The tree nodes don't match its leaves")]
+    InvalidTree,
+    #[error("Invalid leaf encoding: {0}")]
+    InvalidEncoding(String),
+    #[error("The value can't be encoded: {0}")]
+    InvalidValue(String),
+    #[error("Malformed serialized tree")]
+    MalformedBytes,
 }
 
 struct Node<'a> {
@@ -126,7 +373,7 @@ pub fn is_tree_node(layer: &[B256], i: usize) -> bool {
     i < layer.len()
 }
 
-fn hash_pair(a: &B256, b: &B256) -> B256 {
+pub(super) fn hash_pair(a: &B256, b: &B256) -> B256 {
     let mut s = [a.0, b.0];
     s.sort();
     let bytes = s.concat();
@@ -141,6 +388,8 @@ fn right_child_index(i: usize) -> usize {
     2 * i + 1
 }
 
+// `is_multiple_of` would require Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
 fn sibling_index(i: usize) -> usize {
     if i % 2 == 0 {
         // left node
@@ -149,3 +398,160 @@ fn sibling_index(i: usize) -> usize {
         i - 1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use alloy::primitives::{keccak256, B256};
+
+    use super::{MerkleError, MerkleTree};
+
+    fn leaves(count: u64) -> Vec<B256> {
+        (0..count).map(|i| keccak256(i.to_be_bytes())).collect()
+    }
+
+    #[test]
+    fn verify() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let tree = MerkleTree::from_leaves(leaves.iter().copied());
+
+            for leaf in &leaves {
+                let proof = tree.get_proof(leaf).unwrap();
+
+                assert!(MerkleTree::verify(leaf, &proof, &tree.root()));
+                assert!(!MerkleTree::verify(leaf, &proof, &B256::ZERO));
+            }
+        }
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        for count in 0..=9 {
+            let tree = MerkleTree::from_leaves(leaves(count).into_iter());
+            let loaded = MerkleTree::from_bytes(&tree.to_bytes()).unwrap();
+
+            assert_eq!(loaded.layers, tree.layers);
+            assert_eq!(loaded.indexes, tree.indexes);
+        }
+
+        let bytes = MerkleTree::from_leaves(leaves(3).into_iter()).to_bytes();
+        assert!(MerkleTree::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(MerkleTree::from_bytes(&[]).is_err());
+
+        // A leaf count whose node count overflows
+        let mut bytes = vec![super::BYTES_VERSION];
+        bytes.extend_from_slice(&u64::MAX.to_be_bytes());
+        assert!(matches!(
+            MerkleTree::from_bytes(&bytes),
+            Err(MerkleError::MalformedBytes)
+        ));
+    }
+
+    // Whether the verifier can prove the leaves when given in this order, by simulating its
+    // queue of leaves then computed nodes
+    fn provable_in_order(sizes: &[usize], order: &[usize]) -> bool {
+        let top = sizes.len() - 1;
+        let mut known = HashSet::new();
+
+        for &leaf in order {
+            for height in 0..=top {
+                known.insert((height, leaf >> height));
+            }
+        }
+
+        let mut queue = order.iter().map(|&i| (0, i)).collect::<VecDeque<_>>();
+
+        while let Some((mut height, mut index)) = queue.pop_front() {
+            while height < top && index ^ 1 >= sizes[height] {
+                height += 1;
+                index /= 2;
+            }
+
+            if height == top {
+                return queue.is_empty();
+            }
+
+            if known.contains(&(height, index ^ 1))
+                && queue.pop_front() != Some((height, index ^ 1))
+            {
+                return false;
+            }
+
+            queue.push_back((height + 1, index / 2));
+        }
+
+        true
+    }
+
+    fn provable(sizes: &[usize], leaves: &[usize]) -> bool {
+        let mut order = leaves.to_vec();
+
+        // Heap's algorithm, over every order of the leaves
+        let mut counters = vec![0; order.len()];
+        let mut i = 0;
+
+        if provable_in_order(sizes, &order) {
+            return true;
+        }
+
+        while i < order.len() {
+            if counters[i] < i {
+                order.swap(if i % 2 == 0 { 0 } else { counters[i] }, i);
+
+                if provable_in_order(sizes, &order) {
+                    return true;
+                }
+
+                counters[i] += 1;
+                i = 0;
+            } else {
+                counters[i] = 0;
+                i += 1;
+            }
+        }
+
+        false
+    }
+
+    #[test]
+    fn multi_proof() {
+        for count in 1..=8_u64 {
+            let leaves = leaves(count);
+            let tree = MerkleTree::from_leaves(leaves.iter().copied());
+            let sizes = tree.layers.iter().map(Vec::len).collect::<Vec<_>>();
+
+            for subset in 0..(1_u32 << count) {
+                let indexes = (0..count as usize)
+                    .filter(|i| subset & (1 << i) != 0)
+                    .collect::<Vec<_>>();
+                let selected = indexes.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+
+                match tree.get_multi_proof(&selected) {
+                    Ok(multi_proof) => {
+                        assert!(MerkleTree::verify_multi_proof(&multi_proof, &tree.root()));
+
+                        let mut proven = multi_proof.leaves.clone();
+                        let mut expected = selected.clone();
+                        proven.sort();
+                        expected.sort();
+                        assert_eq!(proven, expected);
+                    }
+                    // Only when no order of the leaves can be verified, e.g. when a leaf is
+                    // promoted over several layers
+                    Err(MerkleError::UnsupportedMultiProof) => {
+                        assert!(!provable(&sizes, &indexes), "{count} {indexes:?}")
+                    }
+                    Err(err) => panic!("{err}"),
+                }
+            }
+        }
+
+        // Proven in a different order than the leaves
+        let leaves = leaves(7);
+        let tree = MerkleTree::from_leaves(leaves.iter().copied());
+        let multi_proof = tree.get_multi_proof(&leaves).unwrap();
+        assert!(MerkleTree::verify_multi_proof(&multi_proof, &tree.root()));
+    }
+}
@@ -1,10 +1,31 @@
+mod account_claim;
+pub use account_claim::AccountClaim;
+
+mod claim_book;
+pub use claim_book::ClaimBook;
+
+mod error;
+pub use error::ClaimError;
+
 mod merkle;
-pub use merkle::MerkleTree;
+pub use merkle::{MerkleError, MerkleTree};
+
+mod multi_proof;
+pub use multi_proof::MultiProof;
 
 mod grouped_proposals;
 pub use grouped_proposals::GroupedProposal;
 
+mod proposals;
+pub use proposals::Proposals;
+
 mod proposal_group;
 pub use proposal_group::ProposalGroup;
 
+pub mod snapshot;
+
+mod standard_merkle_tree;
+pub use standard_merkle_tree::{StandardMerkleTree, StandardMerkleTreeData, StandardValue};
+
 mod vote;
+pub use vote::Vote;
@@ -0,0 +1,61 @@
+use alloy::primitives::B256;
+
+use super::merkle::hash_pair;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProof {
+    pub leaves: Vec<B256>,
+    pub proof: Vec<B256>,
+    pub proof_flags: Vec<bool>,
+}
+
+impl MultiProof {
+    /// Computes the root the same way OpenZeppelin's `MerkleProof.processMultiProof` does,
+    /// returning `None` if the proof is malformed.
+    pub fn process(&self) -> Option<B256> {
+        let leaves_len = self.leaves.len();
+        let proof_flags_len = self.proof_flags.len();
+
+        if leaves_len + self.proof.len() != proof_flags_len + 1 {
+            return None;
+        }
+
+        let mut hashes = Vec::with_capacity(proof_flags_len);
+        let mut leaf_pos = 0;
+        let mut hash_pos = 0;
+        let mut proof_pos = 0;
+
+        for &flag in &self.proof_flags {
+            let a = if leaf_pos < leaves_len {
+                leaf_pos += 1;
+                self.leaves[leaf_pos - 1]
+            } else {
+                hash_pos += 1;
+                *hashes.get(hash_pos - 1)?
+            };
+
+            let b = if flag {
+                if leaf_pos < leaves_len {
+                    leaf_pos += 1;
+                    self.leaves[leaf_pos - 1]
+                } else {
+                    hash_pos += 1;
+                    *hashes.get(hash_pos - 1)?
+                }
+            } else {
+                proof_pos += 1;
+                *self.proof.get(proof_pos - 1)?
+            };
+
+            hashes.push(hash_pair(&a, &b));
+        }
+
+        if proof_flags_len > 0 {
+            (proof_pos == self.proof.len()).then(|| hashes[proof_flags_len - 1])
+        } else if leaves_len > 0 {
+            Some(self.leaves[0])
+        } else {
+            self.proof.first().copied()
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    collections::{btree_map, BTreeMap, HashSet},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+
+use alloy::primitives::B256;
+use serde::{Deserialize, Serialize};
+
+use super::{ClaimError, GroupedProposal, ProposalGroup};
+
+const EMBEDDED_PROPOSALS: &str = include_str!("../../proposals/proposals.json");
+
+/// The grouped proposals keyed by tree, in the format of `proposals/proposals.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Proposals(BTreeMap<B256, GroupedProposal>);
+
+impl Proposals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The proposals bundled with the crate.
+    pub fn embedded() -> Self {
+        serde_json::from_str(EMBEDDED_PROPOSALS).expect("The embedded proposals should be valid")
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ClaimError> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ClaimError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, ClaimError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Adds the proposals of `other`, replacing the ones with the same tree, and returns the
+    /// replaced trees.
+    pub fn merge(&mut self, other: Proposals) -> Vec<B256> {
+        let mut replaced = vec![];
+
+        for (tree, proposal) in other.0 {
+            if self.0.insert(tree, proposal).is_some() {
+                replaced.push(tree);
+            }
+        }
+
+        replaced
+    }
+
+    pub fn merge_file<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<B256>, ClaimError> {
+        Ok(self.merge(Self::from_file(path)?))
+    }
+
+    /// Checks that every voter appears once, and that the stored root of every proposal is the
+    /// root of its votes.
+    pub fn validate(&self) -> Result<(), ClaimError> {
+        for (tree, proposal) in &self.0 {
+            let mut voters = HashSet::new();
+
+            for vote in &proposal.votes {
+                if !voters.insert(vote.address) {
+                    return Err(ClaimError::DuplicateVoter {
+                        tree: *tree,
+                        voter: vote.address,
+                    });
+                }
+            }
+
+            let computed = proposal.compute_root();
+
+            if computed != proposal.root {
+                return Err(ClaimError::RootMismatch {
+                    tree: *tree,
+                    root: proposal.root,
+                    computed,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn insert(&mut self, tree: B256, proposal: GroupedProposal) -> Option<GroupedProposal> {
+        self.0.insert(tree, proposal)
+    }
+
+    pub fn get(&self, tree: &B256) -> Option<&GroupedProposal> {
+        self.0.get(tree)
+    }
+
+    pub fn get_by_group(&self, group: &ProposalGroup) -> Option<&GroupedProposal> {
+        self.get(&group.hash())
+    }
+
+    pub fn contains(&self, tree: &B256) -> bool {
+        self.0.contains_key(tree)
+    }
+
+    pub fn trees(&self) -> impl Iterator<Item = &B256> {
+        self.0.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&B256, &GroupedProposal)> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(B256, GroupedProposal)> for Proposals {
+    fn from_iter<I: IntoIterator<Item = (B256, GroupedProposal)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Proposals {
+    type Item = (B256, GroupedProposal);
+    type IntoIter = btree_map::IntoIter<B256, GroupedProposal>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{b256, Address, B256, U256};
+
+    use itertools::Itertools;
+
+    use crate::claim::{ClaimError, GroupedProposal, MerkleTree, ProposalGroup, Vote};
+
+    use super::Proposals;
+
+    #[test]
+    fn embedded() {
+        let proposals = Proposals::embedded();
+
+        proposals.validate().unwrap();
+
+        let group = ProposalGroup::new(vec![
+            b256!("6509ffd1d00d4862e94bd250d7dd0abbb77054c5ab28c289f614362bee805866"),
+            b256!("77de42127551bd8007cf8493b1e584f0775f195a20f37583c5d267da5369aa49"),
+        ]);
+
+        assert!(proposals.get_by_group(&group).is_some());
+    }
+
+    #[test]
+    fn merge_and_validate() {
+        let mut proposals = Proposals::embedded();
+        let (tree, mut proposal) = proposals
+            .iter()
+            .next()
+            .map(|(t, p)| (*t, p.clone()))
+            .unwrap();
+        proposal.votes.pop();
+
+        let newer = [
+            (tree, proposal),
+            (
+                B256::repeat_byte(1),
+                GroupedProposal {
+                    root: B256::ZERO,
+                    votes: vec![],
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(proposals.merge(newer), vec![tree]);
+        assert!(proposals.contains(&B256::repeat_byte(1)));
+        assert!(matches!(
+            proposals.validate(),
+            Err(ClaimError::RootMismatch { tree: t, .. }) if t == tree
+        ));
+    }
+
+    #[test]
+    fn duplicate_voter() {
+        let mut votes = vec![Vote::new(Address::repeat_byte(1), U256::from(1))];
+        votes.push(votes[0].clone());
+
+        let proposals = [(
+            B256::ZERO,
+            GroupedProposal {
+                root: B256::ZERO,
+                votes,
+            },
+        )]
+        .into_iter()
+        .collect::<Proposals>();
+
+        assert!(matches!(
+            proposals.validate(),
+            Err(ClaimError::DuplicateVoter { .. })
+        ));
+    }
+
+    #[test]
+    fn zero_points() {
+        // Voters whose average points round down to zero are hashed like the others, as the
+        // claim-leafs-builder does
+        let votes = vec![
+            Vote::new(Address::repeat_byte(1), U256::from(1)),
+            Vote::new(Address::repeat_byte(2), U256::ZERO),
+            Vote::new(Address::repeat_byte(3), U256::from(2)),
+        ];
+        let leaves = votes.iter().map(B256::from).sorted().collect::<Vec<_>>();
+        let root = MerkleTree::from_leaves(leaves.iter().copied()).root();
+        let proposal = GroupedProposal { root, votes };
+
+        assert_eq!(proposal.compute_root(), root);
+        assert_eq!(proposal.merkle_tree().leaves(), leaves);
+
+        for leaf in &leaves {
+            let proof = proposal.merkle_tree().get_proof(leaf).unwrap();
+            assert!(MerkleTree::verify(leaf, &proof, &root));
+        }
+
+        [(B256::ZERO, proposal)]
+            .into_iter()
+            .collect::<Proposals>()
+            .validate()
+            .unwrap();
+    }
+}
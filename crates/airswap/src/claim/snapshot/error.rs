@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error(transparent)]
+    Request(#[from] cynic::http::CynicReqwestError),
+    #[error("GraphQL errors: {0}")]
+    GraphQl(String),
+    #[error("Empty response")]
+    EmptyResponse,
+    #[error("Invalid proposal id: {0}")]
+    InvalidProposalId(String),
+    #[error("Invalid voter address: {0}")]
+    InvalidVoter(String),
+}
@@ -0,0 +1,12 @@
+mod error;
+mod points;
+mod queries;
+mod snapshot_client;
+
+pub use error::SnapshotError;
+pub use points::aggregate_votes;
+pub use queries::{Proposal, ProposalId, SnapshotVote};
+pub use snapshot_client::{
+    group_proposals, parse_proposal_id, SnapshotClient, SNAPSHOT_API_ENDPOINT, SNAPSHOT_SPACE,
+    SNAPSHOT_START_TIMESTAMP,
+};
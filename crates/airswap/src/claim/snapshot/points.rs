@@ -0,0 +1,105 @@
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::{Address, U256};
+use bigdecimal::{BigDecimal, RoundingMode};
+
+use crate::claim::Vote;
+
+use super::{SnapshotError, SnapshotVote};
+
+const POINTS_DECIMALS: i64 = 4;
+
+/// Aggregates the votes of a proposal group the same way the TypeScript builder does:
+/// only the voters who voted on every proposal qualify, and their points are the average
+/// voting power, with 4 decimals, rounded down.
+pub fn aggregate_votes(
+    votes: &[SnapshotVote],
+    proposal_count: usize,
+) -> Result<Vec<Vote>, SnapshotError> {
+    // (voter, total points, total votes cast), in order of first appearance
+    let mut votes_by_user: Vec<(&str, f64, usize)> = vec![];
+    let mut indexes = HashMap::new();
+
+    for vote in votes {
+        let vp = vote.vp.unwrap_or_default();
+
+        match indexes.get(vote.voter.as_str()) {
+            Some(&i) => {
+                let (_, total_points, total_votes_cast) = &mut votes_by_user[i];
+                *total_points += vp;
+                *total_votes_cast += 1;
+            }
+            None => {
+                indexes.insert(vote.voter.as_str(), votes_by_user.len());
+                votes_by_user.push((&vote.voter, vp, 1));
+            }
+        }
+    }
+
+    votes_by_user
+        .into_iter()
+        .filter(|(_, _, total_votes_cast)| *total_votes_cast == proposal_count)
+        .map(|(voter, total_points, _)| {
+            let address =
+                Address::from_str(voter).map_err(|_| SnapshotError::InvalidVoter(voter.into()))?;
+
+            Ok(Vote::new(
+                address,
+                to_points(total_points / proposal_count as f64),
+            ))
+        })
+        .collect()
+}
+
+// Mirrors `new BigNumber(value).multipliedBy(10 ** 4).toFixed(0, BigNumber.ROUND_FLOOR)`:
+// the float is first converted to its shortest decimal representation, then scaled exactly.
+fn to_points(value: f64) -> U256 {
+    let points = BigDecimal::from_str(&value.to_string()).unwrap_or_default()
+        * BigDecimal::from(10_u64.pow(POINTS_DECIMALS as u32));
+    let points = points.with_scale_round(0, RoundingMode::Floor);
+
+    U256::from_str(&points.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, U256};
+
+    use crate::claim::snapshot::SnapshotVote;
+
+    use super::{aggregate_votes, to_points};
+
+    fn vote(voter: &str, vp: f64) -> SnapshotVote {
+        SnapshotVote {
+            voter: voter.into(),
+            vp: Some(vp),
+            proposal: None,
+        }
+    }
+
+    #[test]
+    fn points() {
+        assert_eq!(to_points(12577.0), U256::from(125770000));
+        // 0.1 + 0.2 is 0.30000000000000004 in floating point
+        assert_eq!(to_points(0.1 + 0.2), U256::from(3000));
+        assert_eq!(to_points(1.23456789), U256::from(12345));
+    }
+
+    #[test]
+    fn only_voters_of_every_proposal_qualify() {
+        let votes = [
+            vote("0xE5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff", 10.0),
+            vote("0xF9FDAd75c01EEd7619D975063Ed232C87577a7fd", 20.0),
+            vote("0xE5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff", 11.0),
+        ];
+
+        let votes = aggregate_votes(&votes, 2).unwrap();
+
+        assert_eq!(votes.len(), 1);
+        assert_eq!(
+            votes[0].address,
+            address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff")
+        );
+        assert_eq!(votes[0].points, U256::from(105000));
+    }
+}
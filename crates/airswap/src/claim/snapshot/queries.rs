@@ -0,0 +1,71 @@
+mod schema {
+    cynic::use_schema!("schemas/snapshot.graphql");
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct ProposalsVariables {
+    pub space: String,
+    pub created_gte: i32,
+    pub first: i32,
+    pub skip: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "schemas/snapshot.graphql",
+    graphql_type = "Query",
+    variables = "ProposalsVariables"
+)]
+pub struct ProposalsQuery {
+    #[arguments(
+        first: $first,
+        skip: $skip,
+        where: { space_in: [$space], created_gte: $created_gte },
+        orderBy: "created",
+        orderDirection: "desc"
+    )]
+    pub proposals: Option<Vec<Option<Proposal>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone, PartialEq)]
+#[cynic(schema_path = "schemas/snapshot.graphql")]
+pub struct Proposal {
+    pub id: String,
+    pub title: String,
+    pub start: i32,
+    pub end: i32,
+    pub snapshot: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct VotesVariables {
+    pub proposal_ids: Option<Vec<Option<String>>>,
+    pub first: i32,
+    pub skip: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "schemas/snapshot.graphql",
+    graphql_type = "Query",
+    variables = "VotesVariables"
+)]
+pub struct VotesQuery {
+    #[arguments(first: $first, skip: $skip, where: { proposal_in: $proposal_ids })]
+    pub votes: Option<Vec<Option<SnapshotVote>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone, PartialEq)]
+#[cynic(schema_path = "schemas/snapshot.graphql", graphql_type = "Vote")]
+pub struct SnapshotVote {
+    pub voter: String,
+    pub vp: Option<f64>,
+    pub proposal: Option<ProposalId>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone, PartialEq)]
+#[cynic(schema_path = "schemas/snapshot.graphql", graphql_type = "Proposal")]
+pub struct ProposalId {
+    pub id: String,
+}
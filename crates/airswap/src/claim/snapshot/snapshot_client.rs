@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+use alloy::primitives::B256;
+use cynic::{http::ReqwestExt, GraphQlResponse, QueryBuilder};
+use itertools::Itertools;
+use reqwest::Client as HttpClient;
+
+use crate::claim::{GroupedProposal, ProposalGroup, Proposals, Vote};
+
+use super::{
+    aggregate_votes,
+    queries::{ProposalsQuery, ProposalsVariables, VotesQuery, VotesVariables},
+    Proposal, SnapshotError, SnapshotVote,
+};
+
+pub const SNAPSHOT_API_ENDPOINT: &str = "https://hub.snapshot.org/graphql";
+pub const SNAPSHOT_SPACE: &str = "vote.airswap.eth";
+pub const SNAPSHOT_START_TIMESTAMP: i32 = 1693609200;
+
+const PROPOSALS_PAGE_SIZE: i32 = 100;
+const VOTES_PAGE_SIZE: i32 = 1000;
+
+#[derive(Debug, Clone)]
+pub struct SnapshotClient {
+    client: HttpClient,
+    endpoint: String,
+    space: String,
+    start_timestamp: i32,
+}
+
+impl SnapshotClient {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            client: HttpClient::new(),
+            endpoint: endpoint.into(),
+            space: String::from(SNAPSHOT_SPACE),
+            start_timestamp: SNAPSHOT_START_TIMESTAMP,
+        }
+    }
+
+    pub fn with_space(mut self, space: impl Into<String>) -> Self {
+        self.space = space.into();
+        self
+    }
+
+    pub fn with_start_timestamp(mut self, start_timestamp: i32) -> Self {
+        self.start_timestamp = start_timestamp;
+        self
+    }
+
+    /// Fetches the proposals, grouped by identical start and end.
+    pub async fn get_proposals(&self) -> Result<Vec<Vec<Proposal>>, SnapshotError> {
+        let mut proposals = vec![];
+
+        loop {
+            let operation = ProposalsQuery::build(ProposalsVariables {
+                space: self.space.clone(),
+                created_gte: self.start_timestamp,
+                first: PROPOSALS_PAGE_SIZE,
+                skip: proposals.len() as i32,
+            });
+
+            let response = self
+                .client
+                .post(&self.endpoint)
+                .run_graphql(operation)
+                .await?;
+            let page = into_data(response)?
+                .proposals
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let page_len = page.len();
+
+            proposals.extend(page);
+
+            if page_len < PROPOSALS_PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(group_proposals(proposals))
+    }
+
+    pub async fn get_snapshot_votes(
+        &self,
+        proposal_ids: &[String],
+    ) -> Result<Vec<SnapshotVote>, SnapshotError> {
+        let mut votes = vec![];
+
+        loop {
+            let operation = VotesQuery::build(VotesVariables {
+                proposal_ids: Some(proposal_ids.iter().cloned().map(Some).collect()),
+                first: VOTES_PAGE_SIZE,
+                skip: votes.len() as i32,
+            });
+
+            let response = self
+                .client
+                .post(&self.endpoint)
+                .run_graphql(operation)
+                .await?;
+            let page = into_data(response)?
+                .votes
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let page_len = page.len();
+
+            votes.extend(page);
+
+            if page_len < VOTES_PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(votes)
+    }
+
+    /// Fetches the votes of a proposal group, with their points aggregated.
+    pub async fn get_votes(&self, proposal_ids: &[String]) -> Result<Vec<Vote>, SnapshotError> {
+        let votes = self.get_snapshot_votes(proposal_ids).await?;
+
+        aggregate_votes(&votes, proposal_ids.len())
+    }
+
+    /// Builds the grouped proposals, keyed by tree, as found in `proposals/proposals.json`.
+    pub async fn get_grouped_proposals(&self) -> Result<Proposals, SnapshotError> {
+        let mut grouped_proposals = Proposals::new();
+
+        for group in self.get_proposals().await? {
+            let ids = group.into_iter().map(|p| p.id).collect::<Vec<_>>();
+            let votes = self.get_votes(&ids).await?;
+            let tree = ProposalGroup::new(
+                ids.iter()
+                    .map(|id| parse_proposal_id(id))
+                    .collect::<Result<_, _>>()?,
+            )
+            .hash();
+            let mut proposal = GroupedProposal {
+                root: B256::ZERO,
+                votes,
+            };
+            proposal.root = proposal.compute_root();
+
+            grouped_proposals.insert(tree, proposal);
+        }
+
+        Ok(grouped_proposals)
+    }
+}
+
+impl Default for SnapshotClient {
+    fn default() -> Self {
+        Self::new(SNAPSHOT_API_ENDPOINT)
+    }
+}
+
+/// Groups the proposals that have the same start and end, keeping the query order.
+pub fn group_proposals(proposals: Vec<Proposal>) -> Vec<Vec<Proposal>> {
+    let mut groups: Vec<Vec<Proposal>> = vec![];
+
+    for proposal in proposals {
+        match groups
+            .iter_mut()
+            .find(|g| g[0].start == proposal.start && g[0].end == proposal.end)
+        {
+            Some(group) => group.push(proposal),
+            None => groups.push(vec![proposal]),
+        }
+    }
+
+    groups
+}
+
+/// Parses a Snapshot proposal id, left padding the ids shorter than 32 bytes.
+pub fn parse_proposal_id(id: &str) -> Result<B256, SnapshotError> {
+    let padded;
+    let id = if id.len() < 66 {
+        padded = format!("0x{:0>64}", id);
+        &padded
+    } else {
+        id
+    };
+
+    B256::from_str(id).map_err(|_| SnapshotError::InvalidProposalId(id.into()))
+}
+
+fn into_data<T>(response: GraphQlResponse<T>) -> Result<T, SnapshotError> {
+    if let Some(errors) = response.errors.filter(|e| !e.is_empty()) {
+        return Err(SnapshotError::GraphQl(
+            errors.iter().map(|e| e.message.clone()).join(", "),
+        ));
+    }
+
+    response.data.ok_or(SnapshotError::EmptyResponse)
+}
@@ -0,0 +1,367 @@
+use std::{collections::HashSet, str::FromStr};
+
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    primitives::{keccak256, Address, B256, U256},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{merkle::hash_pair, MerkleError, MerkleTree, Vote};
+
+const FORMAT: &str = "standard-v1";
+
+/// A Merkle tree in the layout of OpenZeppelin's `StandardMerkleTree`: leaves are the double
+/// keccak256 hash of their ABI encoded values, stored in a complete binary tree.
+#[derive(Debug, Clone)]
+pub struct StandardMerkleTree {
+    tree: Vec<B256>,
+    values: Vec<StandardValue>,
+    leaf_encoding: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardValue {
+    pub value: Vec<Value>,
+    pub tree_index: usize,
+}
+
+/// The JSON dump of a `StandardMerkleTree`, as produced by `StandardMerkleTree.dump()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardMerkleTreeData {
+    pub format: String,
+    pub leaf_encoding: Vec<String>,
+    pub tree: Vec<B256>,
+    pub values: Vec<StandardValue>,
+}
+
+impl StandardMerkleTree {
+    pub fn of(values: Vec<Vec<Value>>, leaf_encoding: Vec<String>) -> Result<Self, MerkleError> {
+        let mut hashed_values = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Ok((i, leaf_hash(value, &leaf_encoding)?)))
+            .collect::<Result<Vec<_>, MerkleError>>()?;
+
+        hashed_values.sort_by_key(|(_, hash)| *hash);
+
+        let tree = make_tree(hashed_values.iter().map(|(_, hash)| *hash).collect());
+        let mut values = values
+            .into_iter()
+            .map(|value| StandardValue {
+                value,
+                tree_index: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for (i, (value_index, _)) in hashed_values.iter().enumerate() {
+            values[*value_index].tree_index = tree.len() - i - 1;
+        }
+
+        Ok(Self {
+            tree,
+            values,
+            leaf_encoding,
+        })
+    }
+
+    pub fn of_votes(votes: &[Vote]) -> Self {
+        let values = votes
+            .iter()
+            .map(|v| {
+                vec![
+                    Value::String(v.address.to_checksum(None)),
+                    Value::String(v.points.to_string()),
+                ]
+            })
+            .collect();
+
+        Self::of(
+            values,
+            vec![String::from("address"), String::from("uint256")],
+        )
+        .expect("Votes should always be encodable")
+    }
+
+    /// Loads a dump without recomputing the tree, see [`StandardMerkleTree::validate`].
+    pub fn load(data: StandardMerkleTreeData) -> Result<Self, MerkleError> {
+        if data.format != FORMAT {
+            return Err(MerkleError::UnknownFormat(data.format));
+        }
+
+        // A complete tree of `n` leaves has `2n - 1` nodes, the leaves being the last `n`
+        if data.tree.len() != (2 * data.values.len()).saturating_sub(1) {
+            return Err(MerkleError::InvalidTree);
+        }
+
+        let leaf_positions = data.tree.len() / 2..data.tree.len();
+        let mut tree_indexes = HashSet::new();
+
+        if !data
+            .values
+            .iter()
+            .all(|v| leaf_positions.contains(&v.tree_index) && tree_indexes.insert(v.tree_index))
+        {
+            return Err(MerkleError::InvalidTree);
+        }
+
+        Ok(Self {
+            tree: data.tree,
+            values: data.values,
+            leaf_encoding: data.leaf_encoding,
+        })
+    }
+
+    pub fn dump(&self) -> StandardMerkleTreeData {
+        StandardMerkleTreeData {
+            format: String::from(FORMAT),
+            leaf_encoding: self.leaf_encoding.clone(),
+            tree: self.tree.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Checks that the leaves match the values and that every node is the hash of its children.
+    pub fn validate(&self) -> Result<(), MerkleError> {
+        for value in &self.values {
+            if leaf_hash(&value.value, &self.leaf_encoding)? != self.tree[value.tree_index] {
+                return Err(MerkleError::InvalidTree);
+            }
+        }
+
+        for i in 0..self.tree.len() {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+
+            if right < self.tree.len()
+                && self.tree[i] != hash_pair(&self.tree[left], &self.tree[right])
+            {
+                return Err(MerkleError::InvalidTree);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn root(&self) -> B256 {
+        self.tree.first().copied().unwrap_or_default()
+    }
+
+    pub fn values(&self) -> &[StandardValue] {
+        &self.values
+    }
+
+    pub fn leaf_encoding(&self) -> &[String] {
+        &self.leaf_encoding
+    }
+
+    pub fn leaf_hash(&self, value: &[Value]) -> Result<B256, MerkleError> {
+        leaf_hash(value, &self.leaf_encoding)
+    }
+
+    /// The proof of the value at `value_index`, verifiable with [`MerkleTree::verify`].
+    pub fn get_proof(&self, value_index: usize) -> Result<Vec<B256>, MerkleError> {
+        let mut index = self
+            .values
+            .get(value_index)
+            .ok_or(MerkleError::LeafNotFound)?
+            .tree_index;
+        let mut proof = vec![];
+
+        while index > 0 {
+            let sibling = if index % 2 == 1 { index + 1 } else { index - 1 };
+            proof.push(self.tree[sibling]);
+            index = (index - 1) / 2;
+        }
+
+        Ok(proof)
+    }
+
+    pub fn verify(&self, value_index: usize, proof: &[B256]) -> Result<bool, MerkleError> {
+        let value = self
+            .values
+            .get(value_index)
+            .ok_or(MerkleError::LeafNotFound)?;
+        let leaf = self.leaf_hash(&value.value)?;
+
+        Ok(MerkleTree::verify(&leaf, proof, &self.root()))
+    }
+
+    /// The values as votes, when the leaves are encoded as `(address, uint256)`.
+    pub fn to_votes(&self) -> Result<Vec<Vote>, MerkleError> {
+        if self.leaf_encoding != ["address", "uint256"] {
+            return Err(MerkleError::InvalidEncoding(self.leaf_encoding.join(",")));
+        }
+
+        self.values
+            .iter()
+            .map(|v| match v.value.as_slice() {
+                [address, points] => Ok(Vote::new(
+                    Address::from_str(&value_to_string(address))
+                        .map_err(|_| MerkleError::InvalidValue(address.to_string()))?,
+                    U256::from_str(&value_to_string(points))
+                        .map_err(|_| MerkleError::InvalidValue(points.to_string()))?,
+                )),
+                _ => Err(MerkleError::InvalidTree),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<StandardMerkleTreeData> for StandardMerkleTree {
+    type Error = MerkleError;
+
+    fn try_from(value: StandardMerkleTreeData) -> Result<Self, Self::Error> {
+        Self::load(value)
+    }
+}
+
+impl From<&StandardMerkleTree> for StandardMerkleTreeData {
+    fn from(value: &StandardMerkleTree) -> Self {
+        value.dump()
+    }
+}
+
+fn leaf_hash(value: &[Value], leaf_encoding: &[String]) -> Result<B256, MerkleError> {
+    if value.len() != leaf_encoding.len() {
+        return Err(MerkleError::InvalidValue(format!("{value:?}")));
+    }
+
+    let values = value
+        .iter()
+        .zip(leaf_encoding)
+        .map(|(value, encoding)| {
+            DynSolType::parse(encoding)
+                .map_err(|_| MerkleError::InvalidEncoding(encoding.clone()))?
+                .coerce_str(&value_to_string(value))
+                .map_err(|_| MerkleError::InvalidValue(value.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let encoded = DynSolValue::Tuple(values).abi_encode_params();
+
+    Ok(keccak256(keccak256(encoded)))
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn make_tree(leaves: Vec<B256>) -> Vec<B256> {
+    if leaves.is_empty() {
+        return vec![];
+    }
+
+    let len = 2 * leaves.len() - 1;
+    let mut tree = vec![B256::ZERO; len];
+
+    for (i, leaf) in leaves.into_iter().enumerate() {
+        tree[len - 1 - i] = leaf;
+    }
+
+    // The first `len / 2` nodes are the inner nodes
+    for i in (0..len / 2).rev() {
+        tree[i] = hash_pair(&tree[2 * i + 1], &tree[2 * i + 2]);
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, b256, U256};
+
+    use crate::claim::Vote;
+
+    use super::{MerkleError, StandardMerkleTree, StandardMerkleTreeData};
+
+    #[test]
+    fn openzeppelin_compatibility() {
+        // Root given by `StandardMerkleTree.of(values, ["address", "uint256"])` in the
+        // OpenZeppelin merkle-tree README
+        let votes = [
+            Vote::new(
+                address!("1111111111111111111111111111111111111111"),
+                U256::from(5000000000000000000_u64),
+            ),
+            Vote::new(
+                address!("2222222222222222222222222222222222222222"),
+                U256::from(2500000000000000000_u64),
+            ),
+        ];
+
+        let tree = StandardMerkleTree::of_votes(&votes);
+
+        assert_eq!(
+            tree.root(),
+            b256!("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77")
+        );
+
+        for i in 0..votes.len() {
+            let proof = tree.get_proof(i).unwrap();
+            assert!(tree.verify(i, &proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn dump_and_load() {
+        let votes = (1..=5_u64)
+            .map(|i| {
+                Vote::new(
+                    address!("1111111111111111111111111111111111111111"),
+                    U256::from(i),
+                )
+            })
+            .collect::<Vec<_>>();
+        let tree = StandardMerkleTree::of_votes(&votes);
+
+        let json = serde_json::to_string(&tree.dump()).unwrap();
+        let data = serde_json::from_str::<StandardMerkleTreeData>(&json).unwrap();
+        let loaded = StandardMerkleTree::load(data).unwrap();
+
+        loaded.validate().unwrap();
+        assert_eq!(loaded.root(), tree.root());
+        assert_eq!(loaded.to_votes().unwrap(), votes);
+    }
+
+    #[test]
+    fn load_malformed() {
+        let votes = (1..=3_u64)
+            .map(|i| {
+                Vote::new(
+                    address!("1111111111111111111111111111111111111111"),
+                    U256::from(i),
+                )
+            })
+            .collect::<Vec<_>>();
+        let data = StandardMerkleTree::of_votes(&votes).dump();
+
+        let mut missing_node = data.clone();
+        missing_node.tree.pop();
+
+        let mut inner_node = data.clone();
+        inner_node.values[0].tree_index = 1;
+
+        let mut duplicate_leaf = data.clone();
+        duplicate_leaf.values[1].tree_index = duplicate_leaf.values[0].tree_index;
+
+        let mut out_of_bounds = data.clone();
+        out_of_bounds.values[0].tree_index = out_of_bounds.tree.len();
+
+        for data in [missing_node, inner_node, duplicate_leaf, out_of_bounds] {
+            assert!(matches!(
+                StandardMerkleTree::load(data),
+                Err(MerkleError::InvalidTree)
+            ));
+        }
+
+        let tree = StandardMerkleTree::load(data).unwrap();
+        for i in 0..votes.len() {
+            assert!(tree.verify(i, &tree.get_proof(i).unwrap()).unwrap());
+        }
+    }
+}
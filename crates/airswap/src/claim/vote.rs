@@ -5,10 +5,10 @@ use alloy::{
     sol,
     sol_types::SolValue,
 };
-use serde::Deserialize;
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 
 sol! {
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
     struct Vote {
         address address;
         uint256 points;
@@ -32,3 +32,16 @@ impl From<&Vote> for B256 {
         keccak256(value.abi_encode_packed())
     }
 }
+
+// Points are serialized as a decimal string, as in `proposals/proposals.json`
+impl Serialize for Vote {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Vote", 2)?;
+        state.serialize_field("address", &self.address)?;
+        state.serialize_field("points", &self.points.to_string())?;
+        state.end()
+    }
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use alloy::primitives::{Address, BlockNumber};
+use serde::{Deserialize, Serialize};
+
+use super::{find_deployment, Config, ConfigError, ProtocolVersion};
+
+const ENV_PREFIX: &str = "AIRSWAP_";
+
+/// A partial config, as read from a file or the environment: the known deployment of the
+/// chain is used for the missing fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigOverrides {
+    pub chain_id: Option<u64>,
+    pub protocol_version: Option<ProtocolVersion>,
+    pub registry_address: Option<Address>,
+    pub registry_from_block: Option<BlockNumber>,
+    pub swap_address: Option<Address>,
+    pub pool_address: Option<Address>,
+    pub staking_address: Option<Address>,
+}
+
+impl ConfigOverrides {
+    /// Reads the `AIRSWAP_` prefixed variables, e.g. `AIRSWAP_CHAIN_ID` or
+    /// `AIRSWAP_SWAP_ADDRESS`.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(std::env::vars())
+    }
+
+    pub fn from_vars<I>(vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides = Self::default();
+
+        for (name, value) in vars {
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+
+            match key {
+                "CHAIN_ID" => overrides.chain_id = Some(parse_var(&name, &value)?),
+                "PROTOCOL_VERSION" => overrides.protocol_version = Some(parse_var(&name, &value)?),
+                "REGISTRY_ADDRESS" => overrides.registry_address = Some(parse_var(&name, &value)?),
+                "REGISTRY_FROM_BLOCK" => {
+                    overrides.registry_from_block = Some(parse_var(&name, &value)?)
+                }
+                "SWAP_ADDRESS" => overrides.swap_address = Some(parse_var(&name, &value)?),
+                "POOL_ADDRESS" => overrides.pool_address = Some(parse_var(&name, &value)?),
+                "STAKING_ADDRESS" => overrides.staking_address = Some(parse_var(&name, &value)?),
+                _ => {}
+            }
+        }
+
+        Ok(overrides)
+    }
+
+    /// Overrides the fields set in `other`.
+    pub fn merge(mut self, other: ConfigOverrides) -> Self {
+        self.chain_id = other.chain_id.or(self.chain_id);
+        self.protocol_version = other.protocol_version.or(self.protocol_version);
+        self.registry_address = other.registry_address.or(self.registry_address);
+        self.registry_from_block = other.registry_from_block.or(self.registry_from_block);
+        self.swap_address = other.swap_address.or(self.swap_address);
+        self.pool_address = other.pool_address.or(self.pool_address);
+        self.staking_address = other.staking_address.or(self.staking_address);
+        self
+    }
+
+    pub fn apply(&self, mut config: Config) -> Config {
+        if let Some(chain_id) = self.chain_id {
+            config.chain_id = chain_id;
+        }
+        if let Some(protocol_version) = self.protocol_version {
+            config.protocol_version = protocol_version;
+        }
+        if let Some(registry_address) = self.registry_address {
+            config.registry_address = registry_address;
+        }
+        if let Some(registry_from_block) = self.registry_from_block {
+            config.registry_from_block = registry_from_block;
+        }
+        if let Some(swap_address) = self.swap_address {
+            config.swap_address = swap_address;
+        }
+        if self.pool_address.is_some() {
+            config.pool_address = self.pool_address;
+        }
+        if self.staking_address.is_some() {
+            config.staking_address = self.staking_address;
+        }
+        config
+    }
+
+    /// Builds the config, from the known deployment of the chain when there is one.
+    pub fn resolve(&self) -> Result<Config, ConfigError> {
+        let chain_id = self.chain_id.ok_or(ConfigError::MissingField("chain_id"))?;
+        let protocol_version = self.protocol_version.unwrap_or_default();
+
+        if let Some(deployment) = find_deployment(chain_id, protocol_version) {
+            return Ok(self.apply(deployment.into()));
+        }
+
+        Ok(Config {
+            chain_id,
+            protocol_version,
+            registry_address: self
+                .registry_address
+                .ok_or(ConfigError::MissingField("registry_address"))?,
+            registry_from_block: self.registry_from_block.unwrap_or_default(),
+            swap_address: self
+                .swap_address
+                .ok_or(ConfigError::MissingField("swap_address"))?,
+            pool_address: self.pool_address,
+            staking_address: self.staking_address,
+        })
+    }
+}
+
+fn parse_var<T: FromStr>(name: &str, value: &str) -> Result<T, ConfigError> {
+    value.parse().map_err(|_| ConfigError::InvalidVariable {
+        name: name.into(),
+        value: value.into(),
+    })
+}
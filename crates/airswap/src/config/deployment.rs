@@ -0,0 +1,15 @@
+use alloy::primitives::{Address, BlockNumber};
+
+use super::ProtocolVersion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    pub chain_id: u64,
+    pub protocol_version: ProtocolVersion,
+    pub registry_address: Address,
+    // The block the registry has been deployed at, its events are fetched from there
+    pub registry_from_block: BlockNumber,
+    pub swap_address: Address,
+    pub pool_address: Option<Address>,
+    pub staking_address: Option<Address>,
+}
@@ -0,0 +1,184 @@
+use std::sync::RwLock;
+
+use alloy::primitives::{address, Address};
+use once_cell::sync::Lazy;
+
+use super::{Deployment, ProtocolVersion};
+
+const REGISTRY_V4: Address = address!("e30E9c001dEFb5F0B04fD21662454A2427F4257A");
+const SWAP_ERC20_V4: Address = address!("d82FA167727a4dc6D6F55830A2c47aBbB4b3a0F8");
+const SWAP_ERC20_V5: Address = address!("D82E10B9A4107939e55fCCa9B53A9ede6CF2fC46");
+pub(crate) const POOL_MAINNET: Address = address!("bbcec987E4C189FCbAB0a2534c77b3ba89229F11");
+const STAKING_MAINNET: Address = address!("9fc450F9AfE2833Eb44f9A1369Ab3678D3929860");
+
+// By protocol version, then chain
+const KNOWN_DEPLOYMENTS: &[Deployment] = &[
+    Deployment {
+        chain_id: 1,
+        protocol_version: ProtocolVersion::Legacy,
+        registry_address: address!("8F9DA6d38939411340b19401E8c54Ea1f51B8f95"),
+        registry_from_block: 12782029,
+        swap_address: address!("522d6f36c95a1b6509a14272c17747bbb582f2a6"),
+        pool_address: None,
+        staking_address: None,
+    },
+    Deployment {
+        chain_id: 137,
+        protocol_version: ProtocolVersion::Legacy,
+        registry_address: address!("9F11691FA842856E44586380b27Ac331ab7De93d"),
+        registry_from_block: 26036024,
+        swap_address: address!("6713c23261c8a9b7d84dd6114e78d9a7b9863c1a"),
+        pool_address: None,
+        staking_address: None,
+    },
+    Deployment {
+        chain_id: 1,
+        protocol_version: ProtocolVersion::V4,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 12782029,
+        swap_address: SWAP_ERC20_V4,
+        pool_address: Some(POOL_MAINNET),
+        staking_address: Some(STAKING_MAINNET),
+    },
+    Deployment {
+        chain_id: 137,
+        protocol_version: ProtocolVersion::V4,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 53161197,
+        swap_address: SWAP_ERC20_V4,
+        pool_address: None,
+        staking_address: None,
+    },
+    Deployment {
+        chain_id: 42161,
+        protocol_version: ProtocolVersion::V4,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 178078567,
+        swap_address: SWAP_ERC20_V4,
+        pool_address: None,
+        staking_address: None,
+    },
+    // The V5 swap contract uses the V4 registry
+    Deployment {
+        chain_id: 1,
+        protocol_version: ProtocolVersion::V5,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 12782029,
+        swap_address: SWAP_ERC20_V5,
+        pool_address: Some(POOL_MAINNET),
+        staking_address: Some(STAKING_MAINNET),
+    },
+    Deployment {
+        chain_id: 137,
+        protocol_version: ProtocolVersion::V5,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 53161197,
+        swap_address: SWAP_ERC20_V5,
+        pool_address: None,
+        staking_address: None,
+    },
+    Deployment {
+        chain_id: 42161,
+        protocol_version: ProtocolVersion::V5,
+        registry_address: REGISTRY_V4,
+        registry_from_block: 178078567,
+        swap_address: SWAP_ERC20_V5,
+        pool_address: None,
+        staking_address: None,
+    },
+];
+
+static CUSTOM_DEPLOYMENTS: Lazy<RwLock<Vec<Deployment>>> = Lazy::new(Default::default);
+
+/// The known deployments of every protocol version.
+pub fn deployments() -> Vec<Deployment> {
+    KNOWN_DEPLOYMENTS.to_vec()
+}
+
+/// The known deployment, without the registered ones, usable in const contexts.
+pub(crate) const fn known_deployment(
+    chain_id: u64,
+    protocol_version: ProtocolVersion,
+) -> Option<Deployment> {
+    let mut i = 0;
+
+    while i < KNOWN_DEPLOYMENTS.len() {
+        let deployment = KNOWN_DEPLOYMENTS[i];
+
+        if deployment.chain_id == chain_id
+            && deployment.protocol_version as u8 == protocol_version as u8
+        {
+            return Some(deployment);
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+/// Registers a deployment, e.g. on a local devnet, taking precedence over the known ones.
+pub fn register_deployment(deployment: Deployment) {
+    let mut custom = CUSTOM_DEPLOYMENTS.write().unwrap();
+
+    custom.retain(|d| {
+        d.chain_id != deployment.chain_id || d.protocol_version != deployment.protocol_version
+    });
+    custom.push(deployment);
+}
+
+pub fn find_deployment(chain_id: u64, protocol_version: ProtocolVersion) -> Option<Deployment> {
+    let matches = |d: &Deployment| d.chain_id == chain_id && d.protocol_version == protocol_version;
+
+    CUSTOM_DEPLOYMENTS
+        .read()
+        .unwrap()
+        .iter()
+        .copied()
+        .find(matches)
+        .or_else(|| known_deployment(chain_id, protocol_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use crate::config::{Deployment, ProtocolVersion};
+
+    use super::{deployments, find_deployment, known_deployment, register_deployment};
+
+    #[test]
+    fn unique_deployments() {
+        let deployments = deployments();
+
+        for (i, d) in deployments.iter().enumerate() {
+            assert!(deployments[i + 1..]
+                .iter()
+                .all(|o| o.chain_id != d.chain_id || o.protocol_version != d.protocol_version));
+            assert_eq!(known_deployment(d.chain_id, d.protocol_version), Some(*d));
+        }
+    }
+
+    #[test]
+    fn custom_deployment() {
+        let deployment = Deployment {
+            chain_id: 31337,
+            protocol_version: ProtocolVersion::V5,
+            registry_address: Address::repeat_byte(1),
+            registry_from_block: 0,
+            swap_address: Address::repeat_byte(2),
+            pool_address: Some(Address::repeat_byte(3)),
+            staking_address: None,
+        };
+
+        assert_eq!(find_deployment(31337, ProtocolVersion::V5), None);
+
+        register_deployment(deployment);
+
+        assert_eq!(
+            find_deployment(31337, ProtocolVersion::V5),
+            Some(deployment)
+        );
+        assert_eq!(find_deployment(31337, ProtocolVersion::V4), None);
+    }
+}
@@ -0,0 +1,213 @@
+use alloy::{
+    contract,
+    network::Network,
+    primitives::Address,
+    providers::Provider,
+    sol,
+    transports::{Transport, TransportError},
+};
+
+use super::{find_deployment, Config, ConfigError, ProtocolVersion};
+
+sol!(
+    #[sol(rpc)]
+    interface ISwapDomain {
+        function DOMAIN_NAME() external view returns (string);
+        function DOMAIN_VERSION() external view returns (string);
+    }
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedDeployment {
+    pub config: Config,
+    pub domain_name: Option<String>,
+    pub domain_version: Option<String>,
+}
+
+impl DetectedDeployment {
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.config.protocol_version
+    }
+}
+
+/// The protocol versions deployed on the chain of the provider, oldest first.
+pub async fn detect_deployments<P, T, N>(
+    provider: &P,
+) -> Result<Vec<DetectedDeployment>, ConfigError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let chain_id = provider.get_chain_id().await?;
+    let mut detected = vec![];
+
+    for protocol_version in ProtocolVersion::ALL {
+        let Some(deployment) = find_deployment(chain_id, protocol_version) else {
+            continue;
+        };
+
+        if let Some(deployment) = detect_deployment(provider, deployment.into()).await? {
+            detected.push(deployment);
+        }
+    }
+
+    Ok(detected)
+}
+
+/// The most recent protocol version deployed on the chain of the provider.
+pub async fn detect_latest<P, T, N>(provider: &P) -> Result<Config, ConfigError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    detect_deployments(provider)
+        .await?
+        .pop()
+        .map(|d| d.config)
+        .ok_or(ConfigError::NoDeployment)
+}
+
+/// Checks that the registry and swap contracts of the config are deployed, and that the swap
+/// contract reports the expected domain version.
+pub async fn detect_deployment<P, T, N>(
+    provider: &P,
+    config: Config,
+) -> Result<Option<DetectedDeployment>, ConfigError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    if !has_code(provider, config.registry_address).await?
+        || !has_code(provider, config.swap_address).await?
+    {
+        return Ok(None);
+    }
+
+    let swap = ISwapDomain::new(config.swap_address, provider);
+    // Older swap contracts don't expose their domain
+    let domain_name = optional(swap.DOMAIN_NAME().call().await.map(|r| r._0))?;
+    let domain_version = optional(swap.DOMAIN_VERSION().call().await.map(|r| r._0))?;
+
+    let matches = domain_matches(config.protocol_version, domain_version.as_deref());
+
+    Ok(matches.then_some(DetectedDeployment {
+        config,
+        domain_name,
+        domain_version,
+    }))
+}
+
+// A call that reverts or returns no data is a missing function, any other failure is an error
+fn optional<T>(result: Result<T, contract::Error>) -> Result<Option<T>, ConfigError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(contract::Error::TransportError(err)) if !is_revert(&err) => Err(err.into()),
+        Err(contract::Error::TransportError(_) | contract::Error::AbiError(_)) => Ok(None),
+        Err(err) => Err(ConfigError::Contract(err)),
+    }
+}
+
+fn is_revert(err: &TransportError) -> bool {
+    err.as_error_resp().is_some_and(|payload| {
+        payload.code == 3 || payload.message.to_lowercase().contains("revert")
+    })
+}
+
+// Whether the major version of the domain is the one of the protocol version
+fn domain_matches(protocol_version: ProtocolVersion, domain_version: Option<&str>) -> bool {
+    match protocol_version.domain_major_version() {
+        Some(major) => domain_version.is_some_and(|v| v.split('.').next() == Some(major)),
+        None => true,
+    }
+}
+
+async fn has_code<P, T, N>(provider: &P, address: Address) -> Result<bool, ConfigError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    Ok(!provider.get_code_at(address).await?.is_empty())
+}
+
+/// Every deployment registered for the chain, whatever is actually deployed.
+pub fn chain_configs(chain_id: u64) -> Vec<Config> {
+    ProtocolVersion::ALL
+        .into_iter()
+        .filter_map(|v| find_deployment(chain_id, v))
+        .map(Config::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        contract,
+        rpc::json_rpc::ErrorPayload,
+        transports::{TransportError, TransportErrorKind},
+    };
+
+    use crate::config::{deployments, ConfigError, ProtocolVersion};
+
+    use super::{chain_configs, domain_matches, optional};
+
+    #[test]
+    fn domain_versions() {
+        assert!(domain_matches(ProtocolVersion::V5, Some("5")));
+        assert!(domain_matches(ProtocolVersion::V5, Some("5.2.1")));
+        assert!(domain_matches(ProtocolVersion::V4, Some("4.3")));
+        assert!(!domain_matches(ProtocolVersion::V5, Some("4.3")));
+        assert!(!domain_matches(ProtocolVersion::V5, Some("51.0")));
+        assert!(!domain_matches(ProtocolVersion::V5, None));
+        assert!(domain_matches(ProtocolVersion::Legacy, None));
+    }
+
+    #[test]
+    fn missing_domain() {
+        let revert = TransportError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: "execution reverted".into(),
+            data: None,
+        });
+        assert!(matches!(
+            optional::<String>(Err(contract::Error::TransportError(revert))),
+            Ok(None)
+        ));
+
+        let timeout = TransportErrorKind::custom_str("request timed out");
+        assert!(matches!(
+            optional::<String>(Err(contract::Error::TransportError(timeout))),
+            Err(ConfigError::Transport(_))
+        ));
+
+        let rate_limit = TransportError::ErrorResp(ErrorPayload {
+            code: -32005,
+            message: "limit exceeded".into(),
+            data: None,
+        });
+        assert!(matches!(
+            optional::<String>(Err(contract::Error::TransportError(rate_limit))),
+            Err(ConfigError::Transport(_))
+        ));
+    }
+
+    #[test]
+    fn chain_configs_are_ordered() {
+        let configs = chain_configs(1);
+
+        assert_eq!(
+            configs
+                .iter()
+                .map(|c| c.protocol_version)
+                .collect::<Vec<_>>(),
+            ProtocolVersion::ALL
+        );
+        assert!(deployments().iter().any(|d| d.chain_id == 42161));
+        assert!(chain_configs(42161)
+            .iter()
+            .all(|c| c.protocol_version != ProtocolVersion::Legacy));
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::transports::TransportError;
+use thiserror::Error;
+
+use super::ProtocolVersion;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("The {protocol_version} protocol isn't deployed on the chain {chain_id}")]
+    UnsupportedChain {
+        chain_id: u64,
+        protocol_version: ProtocolVersion,
+    },
+    #[error("No protocol version is deployed on this chain")]
+    NoDeployment,
+    #[error("The {0} is required when the deployment isn't known")]
+    MissingField(&'static str),
+    #[error("Invalid value for {name}: {value}")]
+    InvalidVariable { name: String, value: String },
+    #[error("Unsupported config file format: {0}")]
+    UnsupportedFormat(String),
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error(transparent)]
+    Contract(alloy::contract::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
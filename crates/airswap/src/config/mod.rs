@@ -0,0 +1,226 @@
+use std::{fs, path::Path};
+
+use alloy::primitives::{Address, BlockNumber};
+use serde::{Deserialize, Serialize};
+
+mod config_overrides;
+pub use config_overrides::ConfigOverrides;
+
+mod detection;
+pub use detection::{
+    chain_configs, detect_deployment, detect_deployments, detect_latest, DetectedDeployment,
+};
+
+mod deployment;
+pub use deployment::Deployment;
+
+mod deployments;
+use deployments::known_deployment;
+pub(crate) use deployments::POOL_MAINNET;
+pub use deployments::{deployments, find_deployment, register_deployment};
+
+mod error;
+pub use error::ConfigError;
+
+mod protocol_version;
+pub use protocol_version::ProtocolVersion;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Config {
+    pub chain_id: u64,
+    pub registry_address: Address,
+    pub registry_from_block: BlockNumber,
+    pub swap_address: Address,
+    pub pool_address: Option<Address>,
+    pub staking_address: Option<Address>,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl Config {
+    /// The known deployment of the protocol version, panicking if there is none.
+    #[deprecated(
+        note = "panics on unknown deployments, use `Config::try_new` or `Config::mainnet_*`"
+    )]
+    pub const fn new(chain_id: u64, protocol_version: ProtocolVersion) -> Self {
+        Self::known(chain_id, protocol_version)
+    }
+
+    /// The deployment of the protocol version, a registered one taking precedence over the
+    /// known one.
+    pub fn try_new(chain_id: u64, protocol_version: ProtocolVersion) -> Result<Self, ConfigError> {
+        find_deployment(chain_id, protocol_version)
+            .map(Self::from_deployment)
+            .ok_or(ConfigError::UnsupportedChain {
+                chain_id,
+                protocol_version,
+            })
+    }
+
+    /// Overrides the pool address, e.g. to use a locally deployed pool.
+    pub const fn with_pool(mut self, pool_address: Address) -> Self {
+        self.pool_address = Some(pool_address);
+        self
+    }
+
+    pub const fn with_registry_from_block(mut self, registry_from_block: BlockNumber) -> Self {
+        self.registry_from_block = registry_from_block;
+        self
+    }
+
+    pub const fn with_staking(mut self, staking_address: Address) -> Self {
+        self.staking_address = Some(staking_address);
+        self
+    }
+
+    /// Loads a TOML or JSON file, depending on its extension. Only `chain_id` is required for
+    /// known deployments, the other fields overriding the deployment ones.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml_str(&content),
+            Some("json") => Self::from_json_str(&content),
+            other => Err(ConfigError::UnsupportedFormat(
+                other.unwrap_or_default().into(),
+            )),
+        }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<Self, ConfigError> {
+        toml::from_str::<ConfigOverrides>(s)?.resolve()
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str::<ConfigOverrides>(s)?.resolve()
+    }
+
+    /// Loads the config from the `AIRSWAP_` prefixed environment variables.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        ConfigOverrides::from_env()?.resolve()
+    }
+
+    /// Applies the overrides set in the `AIRSWAP_` prefixed environment variables.
+    pub fn with_env_overrides(self) -> Result<Self, ConfigError> {
+        Ok(ConfigOverrides::from_env()?.apply(self))
+    }
+
+    pub const fn mainnet_legacy() -> Self {
+        Self::known(1, ProtocolVersion::Legacy)
+    }
+
+    pub const fn mainnet_v4() -> Self {
+        Self::known(1, ProtocolVersion::V4)
+    }
+
+    pub const fn mainnet_v5() -> Self {
+        Self::known(1, ProtocolVersion::V5)
+    }
+
+    // Only called for deployments of the table, never with user input
+    const fn known(chain_id: u64, protocol_version: ProtocolVersion) -> Self {
+        match known_deployment(chain_id, protocol_version) {
+            Some(deployment) => Self::from_deployment(deployment),
+            None => panic!("The protocol version isn't deployed on the chain"),
+        }
+    }
+
+    const fn from_deployment(deployment: Deployment) -> Self {
+        Self {
+            chain_id: deployment.chain_id,
+            registry_address: deployment.registry_address,
+            registry_from_block: deployment.registry_from_block,
+            swap_address: deployment.swap_address,
+            pool_address: deployment.pool_address,
+            staking_address: deployment.staking_address,
+            protocol_version: deployment.protocol_version,
+        }
+    }
+}
+
+impl From<Deployment> for Config {
+    fn from(deployment: Deployment) -> Self {
+        Self::from_deployment(deployment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use super::{Config, ConfigError, ConfigOverrides, ProtocolVersion};
+
+    #[test]
+    fn from_toml() {
+        let config = Config::from_toml_str(
+            r#"
+            chain_id = 1
+            protocol_version = "v5"
+            registry_from_block = 20000000
+            pool_address = "0x0101010101010101010101010101010101010101"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config::mainnet_v5()
+                .with_pool(Address::repeat_byte(1))
+                .with_registry_from_block(20000000)
+        );
+    }
+
+    #[test]
+    fn private_deployment() {
+        let json = r#"{ "chain_id": 31338, "protocol_version": "v4" }"#;
+
+        assert!(matches!(
+            Config::from_json_str(json),
+            Err(ConfigError::MissingField("registry_address"))
+        ));
+
+        let config = ConfigOverrides::from_vars([
+            ("AIRSWAP_CHAIN_ID".to_string(), "31338".to_string()),
+            ("AIRSWAP_PROTOCOL_VERSION".to_string(), "v4".to_string()),
+            (
+                "AIRSWAP_REGISTRY_ADDRESS".to_string(),
+                Address::repeat_byte(2).to_string(),
+            ),
+            (
+                "AIRSWAP_SWAP_ADDRESS".to_string(),
+                Address::repeat_byte(3).to_string(),
+            ),
+        ])
+        .unwrap()
+        .resolve()
+        .unwrap();
+
+        assert_eq!(config.protocol_version, ProtocolVersion::V4);
+        assert_eq!(config.swap_address, Address::repeat_byte(3));
+
+        let roundtrip = serde_json::from_str::<Config>(&serde_json::to_string(&config).unwrap());
+        assert_eq!(roundtrip.unwrap(), config);
+    }
+
+    #[test]
+    fn const_and_fallible_constructors() {
+        const MAINNET: Config = Config::mainnet_v5();
+
+        assert_eq!(Config::try_new(1, ProtocolVersion::V5).unwrap(), MAINNET);
+        assert_eq!(
+            Config::try_new(1, ProtocolVersion::Legacy).unwrap(),
+            Config::mainnet_legacy()
+        );
+        assert_eq!(
+            Config::try_new(1, ProtocolVersion::V4).unwrap(),
+            Config::mainnet_v4()
+        );
+        assert!(matches!(
+            Config::try_new(31339, ProtocolVersion::V5),
+            Err(ConfigError::UnsupportedChain {
+                chain_id: 31339,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProtocolVersion {
+    #[default]
+    Legacy,
+    V4,
+    V5,
+}
+
+impl ProtocolVersion {
+    pub const ALL: [ProtocolVersion; 3] = [
+        ProtocolVersion::Legacy,
+        ProtocolVersion::V4,
+        ProtocolVersion::V5,
+    ];
+
+    /// The major version in the EIP-712 domain of the swap contract, when it has one.
+    pub fn domain_major_version(&self) -> Option<&'static str> {
+        match self {
+            ProtocolVersion::Legacy => None,
+            ProtocolVersion::V4 => Some("4"),
+            ProtocolVersion::V5 => Some("5"),
+        }
+    }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolVersion::Legacy => write!(f, "legacy"),
+            ProtocolVersion::V4 => write!(f, "v4"),
+            ProtocolVersion::V5 => write!(f, "v5"),
+        }
+    }
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "legacy" => Ok(ProtocolVersion::Legacy),
+            "v4" => Ok(ProtocolVersion::V4),
+            "v5" => Ok(ProtocolVersion::V5),
+            other => Err(format!("The version '{other}' is not supported")),
+        }
+    }
+}
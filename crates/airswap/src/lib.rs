@@ -1,19 +1,39 @@
 mod maker;
 pub use maker::{
     build_buy_order, build_sell_order, json_rpc, MakerClient, MakerError, MakerService,
-    ThresholdLayer,
+    QuoteComparator, QuoteSide, ThresholdLayer,
 };
 
+pub mod analytics;
+
 #[cfg(feature = "claim")]
 pub mod claim;
 
 mod config;
-pub use config::{Config, ProtocolVersion};
+pub use config::{
+    chain_configs, deployments, detect_deployment, detect_deployments, detect_latest,
+    find_deployment, register_deployment, Config, ConfigError, ConfigOverrides, Deployment,
+    DetectedDeployment, ProtocolVersion,
+};
 
 pub mod pool;
 
 mod registry;
-pub use registry::{Maker, MakerWithSupportedTokens, RegistryClient, RegistryError};
+#[allow(deprecated)]
+pub use registry::KNOWN_MAKERS;
+pub use registry::{
+    load_maker_directory, lookup_ens_name, maker_info, maker_name, merge_makers, namehash,
+    register_maker_info, BatchOptions, CachedRegistry, LegacyRegistry, Maker, MakerBatch,
+    MakerDirectory, MakerInfo, MakerRegistration, MakerTransport, MakerUrl, MakerUrlError,
+    MakerWithSupportedTokens, MergedMaker, MultiRegistryClient, RegistryCacheKey, RegistryClient,
+    RegistryContract, RegistryError, RegistryV4, RegistryV4Contract, RegistryV4ContractErrors,
+    StaticRegistry, VersionedMaker, ENS_REGISTRY_ADDRESS,
+};
 
 mod swap;
-pub use swap::{get_swap_events, get_swap_events_stream, SwapERC20Contract, SwapError};
+pub use swap::{
+    fill_timestamps, get_swap_contract_history, get_swap_events, get_swap_events_stream,
+    get_swap_history, get_swap_history_with_timestamps, watch_swap_contract_events,
+    watch_swap_events, EventRecord, StreamEvent, SwapContractEvent, SwapERC20Contract, SwapError,
+    SwapRecord,
+};
@@ -28,6 +28,11 @@ impl MakerClient {
         }
     }
 
+    /// Quotes on the chain and swap contract of the config.
+    pub fn from_config(maker: MakerWithSupportedTokens, config: Config) -> Self {
+        Self::new(config.chain_id, maker, config)
+    }
+
     pub async fn get_protocols(&self) -> Result<ProtocolsPayload, MakerError> {
         let payload = self.post(Payload::Protocols).await?.try_into()?;
 
@@ -1,7 +1,7 @@
 use reqwest::StatusCode;
 use thiserror::Error;
 
-use crate::json_rpc::ResponseDecodeError;
+use crate::{json_rpc::ResponseDecodeError, MakerTransport, MakerUrlError};
 
 use super::service::BelowThresholdError;
 
@@ -20,5 +20,9 @@ pub enum MakerError {
     #[error("The pair is not supported")]
     PairNotSupported,
     #[error(transparent)]
+    InvalidUrl(#[from] MakerUrlError),
+    #[error("The {0} transport is not supported")]
+    UnsupportedTransport(MakerTransport),
+    #[error(transparent)]
     AmountTooLow(#[from] BelowThresholdError),
 }
@@ -3,7 +3,7 @@ use serde::Deserialize;
 
 use super::{Response, ResponseDecodeError, ResponseResult, ResultPayload, Signature};
 
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderPayload {
     pub nonce: U256,
@@ -22,26 +22,6 @@ pub struct OrderPayload {
     pub v: Option<U64>,
 }
 
-impl PartialEq for OrderPayload {
-    fn eq(&self, other: &Self) -> bool {
-        self.signer_amount == other.signer_amount
-    }
-}
-
-impl Eq for OrderPayload {}
-
-impl PartialOrd for OrderPayload {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for OrderPayload {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.signer_amount.cmp(&other.signer_amount)
-    }
-}
-
 impl TryFrom<Response> for OrderPayload {
     type Error = ResponseDecodeError;
 
@@ -65,34 +45,3 @@ impl TryFrom<ResponseResult> for OrderPayload {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use alloy::primitives::U256;
-
-    use super::OrderPayload;
-
-    #[test]
-    fn sort() {
-        let mut orders = vec![
-            OrderPayload {
-                signer_amount: U256::from(5000),
-                ..Default::default()
-            },
-            OrderPayload {
-                signer_amount: U256::from(10000),
-                ..Default::default()
-            },
-            OrderPayload {
-                signer_amount: U256::from(3000),
-                ..Default::default()
-            },
-        ];
-
-        orders.sort();
-
-        let best_order = orders.last().unwrap();
-
-        assert_eq!(best_order.signer_amount.to_string(), "10000")
-    }
-}
@@ -1,11 +1,13 @@
 mod client;
 mod error;
 pub mod json_rpc;
+mod quote_comparator;
 mod service;
 
 use alloy::primitives::{Address, U256};
 pub use client::MakerClient;
 pub use error::MakerError;
+pub use quote_comparator::{QuoteComparator, QuoteSide};
 pub use service::{MakerService, ThresholdLayer};
 
 use self::json_rpc::{OrderParams, SenderSideOrderParams, SignerSideOrderParams};
@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+
+use alloy::primitives::{U256, U512};
+
+use super::json_rpc::OrderPayload;
+
+const FEE_DIVISOR: u16 = 10000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuoteSide {
+    // The sender amount is fixed, the best quote maximizes the signer amount
+    Sell,
+    // The signer amount is fixed, the best quote minimizes the sender amount
+    Buy,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuoteComparator {
+    side: QuoteSide,
+    protocol_fee: u16,
+    gas_cost: Option<U256>,
+}
+
+impl QuoteComparator {
+    pub fn new(side: QuoteSide) -> Self {
+        Self {
+            side,
+            protocol_fee: 0,
+            gas_cost: None,
+        }
+    }
+
+    pub fn sell() -> Self {
+        Self::new(QuoteSide::Sell)
+    }
+
+    pub fn buy() -> Self {
+        Self::new(QuoteSide::Buy)
+    }
+
+    /// Protocol fee in basis points, used for the orders without a `signer_fee`.
+    pub fn with_protocol_fee(mut self, protocol_fee: u16) -> Self {
+        self.protocol_fee = protocol_fee.min(FEE_DIVISOR);
+        self
+    }
+
+    /// Gas cost estimate, expressed in the signer token for sells and in the
+    /// sender token for buys.
+    pub fn with_gas_cost(mut self, gas_cost: U256) -> Self {
+        self.gas_cost = Some(gas_cost);
+        self
+    }
+
+    pub fn side(&self) -> QuoteSide {
+        self.side
+    }
+
+    /// Protocol fee of the order in basis points, falling back to the configured one.
+    pub fn fee_bps(&self, order: &OrderPayload) -> u16 {
+        order
+            .signer_fee
+            .as_deref()
+            .and_then(|fee| fee.trim().parse::<u16>().ok())
+            .unwrap_or(self.protocol_fee)
+            .min(FEE_DIVISOR)
+    }
+
+    /// Protocol fee charged to the signer, in the signer token, on top of the signer amount.
+    pub fn fee(&self, order: &OrderPayload) -> U256 {
+        order.signer_amount * U256::from(self.fee_bps(order)) / U256::from(FEE_DIVISOR)
+    }
+
+    /// Amount of signer token leaving the signer wallet, fee included.
+    pub fn signer_cost(&self, order: &OrderPayload) -> U256 {
+        order.signer_amount.saturating_add(self.fee(order))
+    }
+
+    /// Amount of signer token received by the sender, net of gas. The protocol fee is paid by
+    /// the signer, so the sender receives the whole signer amount.
+    pub fn received(&self, order: &OrderPayload) -> U256 {
+        match (self.side, self.gas_cost) {
+            (QuoteSide::Sell, Some(gas_cost)) => order.signer_amount.saturating_sub(gas_cost),
+            _ => order.signer_amount,
+        }
+    }
+
+    /// Amount of sender token paid by the sender, including gas.
+    pub fn paid(&self, order: &OrderPayload) -> U256 {
+        match (self.side, self.gas_cost) {
+            (QuoteSide::Buy, Some(gas_cost)) => order.sender_amount.saturating_add(gas_cost),
+            _ => order.sender_amount,
+        }
+    }
+
+    /// Compares two quotes by effective price, `Ordering::Greater` meaning `a` is better.
+    pub fn compare(&self, a: &OrderPayload, b: &OrderPayload) -> Ordering {
+        // received(a) / paid(a) vs received(b) / paid(b), cross multiplied to stay exact
+        let a_rate = U512::from(self.received(a)) * U512::from(self.paid(b));
+        let b_rate = U512::from(self.received(b)) * U512::from(self.paid(a));
+
+        match (self.paid(a).is_zero(), self.paid(b).is_zero()) {
+            (true, true) => self.received(a).cmp(&self.received(b)),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => a_rate.cmp(&b_rate).then_with(|| match self.side {
+                QuoteSide::Sell => self.received(a).cmp(&self.received(b)),
+                QuoteSide::Buy => self.paid(b).cmp(&self.paid(a)),
+            }),
+        }
+    }
+
+    pub fn best<'a, I>(&self, quotes: I) -> Option<&'a OrderPayload>
+    where
+        I: IntoIterator<Item = &'a OrderPayload>,
+    {
+        quotes.into_iter().max_by(|a, b| self.compare(a, b))
+    }
+
+    /// Sorts the quotes from the best to the worst.
+    pub fn sort(&self, quotes: &mut [OrderPayload]) {
+        quotes.sort_by(|a, b| self.compare(b, a));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+
+    use crate::json_rpc::OrderPayload;
+
+    use super::QuoteComparator;
+
+    fn order(signer_amount: u64, sender_amount: u64) -> OrderPayload {
+        OrderPayload {
+            signer_amount: U256::from(signer_amount),
+            sender_amount: U256::from(sender_amount),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sort_sell() {
+        let mut orders = vec![order(5000, 1000), order(10000, 1000), order(3000, 1000)];
+
+        QuoteComparator::sell().sort(&mut orders);
+
+        assert_eq!(orders.first().unwrap().signer_amount.to_string(), "10000")
+    }
+
+    #[test]
+    fn sort_buy() {
+        let mut orders = vec![order(1000, 5000), order(1000, 3000), order(1000, 10000)];
+
+        QuoteComparator::buy().sort(&mut orders);
+
+        assert_eq!(orders.first().unwrap().sender_amount.to_string(), "3000")
+    }
+
+    #[test]
+    fn gas_cost() {
+        let orders = [order(1000, 100), order(500, 49)];
+
+        // Without gas, the second quote has the best price
+        let best = QuoteComparator::buy().best(&orders).unwrap();
+        assert_eq!(best, &orders[1]);
+
+        // A fixed gas cost weights more on the smaller quote
+        let best = QuoteComparator::buy()
+            .with_gas_cost(U256::from(20))
+            .best(&orders)
+            .unwrap();
+        assert_eq!(best, &orders[0]);
+    }
+
+    #[test]
+    fn signer_fee() {
+        let mut with_fee = order(10000, 1000);
+        with_fee.signer_fee = Some(String::from("7"));
+        let without_fee = order(10000, 1000);
+
+        let comparator = QuoteComparator::sell().with_protocol_fee(5);
+
+        // The fee is charged to the signer, on top of the signer amount
+        assert_eq!(comparator.received(&with_fee), U256::from(10000));
+        assert_eq!(comparator.fee(&with_fee), U256::from(7));
+        assert_eq!(comparator.signer_cost(&with_fee), U256::from(10007));
+        assert_eq!(comparator.fee(&without_fee), U256::from(5));
+        assert_eq!(
+            comparator.compare(&with_fee, &without_fee),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn structural_equality() {
+        assert_ne!(order(1000, 100), order(1000, 200));
+    }
+}
@@ -69,9 +69,19 @@ impl Service<Payload> for MakerService {
             return Box::pin(ready(Err(MakerError::PairNotSupported)));
         }
 
+        let url = match self.maker.maker.parse_url() {
+            Ok(url) if url.transport().is_http() => url,
+            Ok(url) => {
+                return Box::pin(ready(Err(MakerError::UnsupportedTransport(
+                    url.transport(),
+                ))))
+            }
+            Err(err) => return Box::pin(ready(Err(err.into()))),
+        };
+
         let fut = self
             .client
-            .post(self.maker.url())
+            .post(url.url().clone())
             .json(&Request::from(payload))
             .send()
             .map_err(Into::into)
@@ -0,0 +1,194 @@
+use std::marker::PhantomData;
+
+use alloy::{
+    network::{Network, ReceiptResponse},
+    primitives::{Address, TxHash, B256, U256},
+    providers::Provider,
+    transports::Transport,
+};
+
+use crate::{claim::Proposals, Config};
+
+use super::{EnableTransaction, PoolContractInstance, PoolError, TreeStatus};
+
+pub struct PoolAdmin<P, T, N> {
+    instance: PoolContractInstance<T, P, N>,
+    admin: Address,
+    phantom: PhantomData<(T, N)>,
+}
+
+impl<P, T, N> PoolAdmin<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    pub fn new(provider: P, pool_address: Address, admin: Address) -> Self {
+        Self {
+            instance: PoolContractInstance::new(pool_address, provider),
+            admin,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn from_config(provider: P, config: &Config, admin: Address) -> Result<Self, PoolError> {
+        let pool_address = config
+            .pool_address
+            .ok_or(PoolError::PoolNotDeployed(config.protocol_version))?;
+
+        Ok(Self::new(provider, pool_address, admin))
+    }
+
+    pub fn address(&self) -> &Address {
+        self.instance.address()
+    }
+
+    pub fn admin(&self) -> &Address {
+        &self.admin
+    }
+
+    /// The root enabled for the tree, zero if it isn't enabled.
+    pub async fn get_root(&self, tree: B256) -> Result<B256, PoolError> {
+        Ok(self.instance.rootsByTree(tree).call().await?._0)
+    }
+
+    /// Verifies the roots of the proposals, then compares them with the enabled ones.
+    pub async fn get_tree_statuses(
+        &self,
+        proposals: &Proposals,
+    ) -> Result<Vec<TreeStatus>, PoolError> {
+        proposals.validate()?;
+
+        let mut statuses = vec![];
+
+        for (tree, proposal) in proposals.iter() {
+            statuses.push(TreeStatus {
+                tree: *tree,
+                root: proposal.root,
+                enabled_root: self.get_root(*tree).await?,
+            });
+        }
+
+        Ok(statuses)
+    }
+
+    /// The enable transactions of the trees not enabled yet, each checked with an `eth_call`.
+    pub async fn prepare_enables(
+        &self,
+        proposals: &Proposals,
+    ) -> Result<Vec<EnableTransaction<N>>, PoolError> {
+        let mut transactions = vec![];
+
+        for status in self.get_tree_statuses(proposals).await? {
+            if status.is_pending() {
+                self.dry_run_enable(status.tree, status.root).await?;
+
+                transactions.push(EnableTransaction {
+                    tree: status.tree,
+                    root: status.root,
+                    request: self.enable_request(status.tree, status.root),
+                });
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    pub async fn dry_run_enable(&self, tree: B256, root: B256) -> Result<(), PoolError> {
+        self.instance
+            .enable(tree, root)
+            .from(self.admin)
+            .call()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn dry_run_enable_and_set_claimed(
+        &self,
+        tree: B256,
+        root: B256,
+        accounts: Vec<Address>,
+    ) -> Result<(), PoolError> {
+        self.instance
+            .enableAndSetClaimed(tree, root, accounts)
+            .from(self.admin)
+            .call()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends the transaction enabling the tree, the provider being expected to sign for the
+    /// admin.
+    pub async fn enable(&self, tree: B256, root: B256) -> Result<TxHash, PoolError> {
+        let receipt = self
+            .instance
+            .enable(tree, root)
+            .from(self.admin)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        if !receipt.status() {
+            return Err(PoolError::Reverted(receipt.transaction_hash()));
+        }
+
+        Ok(receipt.transaction_hash())
+    }
+
+    pub fn enable_request(&self, tree: B256, root: B256) -> N::TransactionRequest {
+        self.instance
+            .enable(tree, root)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn enable_and_set_claimed_request(
+        &self,
+        tree: B256,
+        root: B256,
+        accounts: Vec<Address>,
+    ) -> N::TransactionRequest {
+        self.instance
+            .enableAndSetClaimed(tree, root, accounts)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn set_scale_request(&self, scale: U256) -> N::TransactionRequest {
+        self.instance
+            .setScale(scale)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn set_max_request(&self, max: U256) -> N::TransactionRequest {
+        self.instance
+            .setMax(max)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn set_admin_request(&self, admin: Address) -> N::TransactionRequest {
+        self.instance
+            .setAdmin(admin)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn unset_admin_request(&self, admin: Address) -> N::TransactionRequest {
+        self.instance
+            .unsetAdmin(admin)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+
+    pub fn drain_to_request(&self, tokens: Vec<Address>, dest: Address) -> N::TransactionRequest {
+        self.instance
+            .drainTo(tokens, dest)
+            .from(self.admin)
+            .into_transaction_request()
+    }
+}
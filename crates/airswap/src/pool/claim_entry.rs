@@ -0,0 +1,19 @@
+use alloy::primitives::{Address, BlockNumber, TxHash, B256, U256};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimEntry {
+    pub account: Address,
+    pub tree: B256,
+    // The withdrawn token and its recipient, unless the claim was only marked as used, as
+    // done by `enableAndSetClaimed`
+    pub token: Option<Address>,
+    pub recipient: Option<Address>,
+    // The points of the account in the tree, when the tree is in the claim book
+    pub value: Option<U256>,
+    // The share of the withdrawn amount coming from this tree, when withdrawn and its value is
+    // known
+    pub amount: Option<U256>,
+    pub block_number: BlockNumber,
+    pub timestamp: Option<u64>,
+    pub transaction_hash: TxHash,
+}
@@ -0,0 +1,229 @@
+use std::marker::PhantomData;
+
+use alloy::{
+    network::{Network, ReceiptResponse},
+    primitives::{Address, B256, U256},
+    providers::Provider,
+    rpc::types::eth::Filter,
+    sol_types::SolEvent,
+    transports::Transport,
+};
+
+use crate::{
+    claim::{AccountClaim, ClaimBook},
+    Config,
+};
+
+use super::{
+    Claim, PoolContractInstance, PoolError, RewardSimulator, UseClaim, Withdraw, Withdrawal, IERC20,
+};
+
+const SLIPPAGE_DIVISOR: u16 = 10000;
+
+pub struct PoolClient<P, T, N> {
+    instance: PoolContractInstance<T, P, N>,
+    claim_book: ClaimBook,
+    phantom: PhantomData<(T, N)>,
+}
+
+impl<P, T, N> PoolClient<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    pub fn new(provider: P, pool_address: Address, claim_book: ClaimBook) -> Self {
+        Self {
+            instance: PoolContractInstance::new(pool_address, provider),
+            claim_book,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn from_config(
+        provider: P,
+        config: &Config,
+        claim_book: ClaimBook,
+    ) -> Result<Self, PoolError> {
+        let pool_address = config
+            .pool_address
+            .ok_or(PoolError::PoolNotDeployed(config.protocol_version))?;
+
+        Ok(Self::new(provider, pool_address, claim_book))
+    }
+
+    pub fn address(&self) -> &Address {
+        self.instance.address()
+    }
+
+    pub fn claim_book(&self) -> &ClaimBook {
+        &self.claim_book
+    }
+
+    /// The claims of the account that haven't been used yet, on trees enabled with the root of
+    /// the claim.
+    pub async fn get_claimable(&self, account: Address) -> Result<Vec<AccountClaim>, PoolError> {
+        let claims = self.claim_book.get_claims(account)?;
+
+        if claims.is_empty() {
+            return Ok(claims);
+        }
+
+        let trees = claims.iter().map(|c| c.tree).collect();
+        let status = self.instance.getStatus(account, trees).call().await?._0;
+        let mut roots = Vec::with_capacity(claims.len());
+
+        for claim in &claims {
+            roots.push(self.instance.rootsByTree(claim.tree).call().await?._0);
+        }
+
+        Ok(claimable(claims, &status, &roots))
+    }
+
+    /// The amount of `token` the claims would withdraw.
+    pub async fn preview(
+        &self,
+        claims: &[AccountClaim],
+        token: Address,
+    ) -> Result<U256, PoolError> {
+        let value = claims.iter().map(|c| c.value).sum();
+        let amount = self.instance.calculate(value, token).call().await?._0;
+
+        Ok(amount)
+    }
+
+    /// A simulator using the current scale, max and balances of the pool.
+    pub async fn reward_simulator(&self, tokens: &[Address]) -> Result<RewardSimulator, PoolError> {
+        let scale = self.instance.scale().call().await?._0;
+        let max = self.instance.max().call().await?._0;
+        let mut simulator = RewardSimulator::new(scale, max);
+
+        for token in tokens {
+            let balance = IERC20::new(*token, self.instance.provider())
+                .balanceOf(*self.address())
+                .call()
+                .await?
+                ._0;
+
+            simulator = simulator.with_balance(*token, balance);
+        }
+
+        Ok(simulator)
+    }
+
+    /// Withdraws every unused claim of the account, accepting to receive down to
+    /// `slippage` basis points less than the previewed amount.
+    pub async fn withdraw(
+        &self,
+        account: Address,
+        token: Address,
+        slippage: u16,
+        recipient: Address,
+    ) -> Result<Withdrawal, PoolError> {
+        let claims = self.get_claimable(account).await?;
+
+        if claims.is_empty() {
+            return Err(PoolError::NothingToClaim);
+        }
+
+        let amount = self.preview(&claims, token).await?;
+        let minimum = amount * U256::from(SLIPPAGE_DIVISOR - slippage.min(SLIPPAGE_DIVISOR))
+            / U256::from(SLIPPAGE_DIVISOR);
+        let claims = claims.into_iter().map(Claim::from).collect();
+
+        let receipt = self
+            .instance
+            .withdraw(claims, token, minimum, recipient)
+            .from(account)
+            .send()
+            .await?
+            .get_receipt()
+            .await?;
+
+        let transaction_hash = receipt.transaction_hash();
+
+        if !receipt.status() {
+            return Err(PoolError::Reverted(transaction_hash));
+        }
+
+        let mut filter = Filter::new().address(*self.address());
+
+        filter = match receipt.block_hash() {
+            Some(block_hash) => filter.at_block_hash(block_hash),
+            None => filter.select(receipt.block_number().unwrap_or_default()),
+        };
+
+        let logs = self
+            .instance
+            .provider()
+            .get_logs(&filter)
+            .await?
+            .into_iter()
+            .filter(|l| l.transaction_hash == Some(transaction_hash))
+            .collect::<Vec<_>>();
+
+        let mut withdraw = None;
+        let mut use_claims = vec![];
+
+        for log in logs {
+            match log.topic0() {
+                Some(&Withdraw::SIGNATURE_HASH) => {
+                    withdraw = Some(Withdraw::decode_log_data(log.data(), true)?)
+                }
+                Some(&UseClaim::SIGNATURE_HASH) => {
+                    use_claims.push(UseClaim::decode_log_data(log.data(), true)?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Withdrawal {
+            transaction_hash,
+            withdraw: withdraw.ok_or(PoolError::MissingWithdrawEvent(transaction_hash))?,
+            use_claims,
+        })
+    }
+}
+
+// The unused claims whose root is the enabled one of their tree
+fn claimable(claims: Vec<AccountClaim>, status: &[bool], roots: &[B256]) -> Vec<AccountClaim> {
+    claims
+        .into_iter()
+        .zip(status.iter().zip(roots))
+        .filter(|(claim, (claimed, root))| !**claimed && claim.root == **root)
+        .map(|(claim, _)| claim)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{B256, U256};
+
+    use crate::claim::AccountClaim;
+
+    use super::claimable;
+
+    fn claim(tree: u8, root: u8) -> AccountClaim {
+        AccountClaim {
+            tree: B256::repeat_byte(tree),
+            root: B256::repeat_byte(root),
+            value: U256::from(1),
+            proof: vec![],
+        }
+    }
+
+    #[test]
+    fn enabled_unused_claims() {
+        let claims = vec![claim(1, 1), claim(2, 2), claim(3, 3), claim(4, 4)];
+        let status = [false, true, false, false];
+        // The third tree is enabled with another root, the fourth isn't enabled
+        let roots = [
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(5),
+            B256::ZERO,
+        ];
+
+        assert_eq!(claimable(claims, &status, &roots), vec![claim(1, 1)]);
+    }
+}
@@ -1,3 +1,5 @@
-use alloy::primitives::{address, Address};
+use alloy::primitives::Address;
 
-pub const POLL_ADDRESS: Address = address!("bbcec987E4C189FCbAB0a2534c77b3ba89229F11");
+/// The pool of the mainnet deployments.
+#[deprecated(note = "use `Config::pool_address` instead")]
+pub const POLL_ADDRESS: Address = crate::config::POOL_MAINNET;
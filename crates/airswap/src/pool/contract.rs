@@ -2,6 +2,14 @@ use alloy::sol;
 
 sol!(
     #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
     PoolContract,
     "abi/pool.json"
 );
+
+sol!(
+    #[sol(rpc)]
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+);
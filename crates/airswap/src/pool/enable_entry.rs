@@ -0,0 +1,10 @@
+use alloy::primitives::{BlockNumber, TxHash, B256};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnableEntry {
+    pub tree: B256,
+    pub root: B256,
+    pub block_number: BlockNumber,
+    pub timestamp: Option<u64>,
+    pub transaction_hash: TxHash,
+}
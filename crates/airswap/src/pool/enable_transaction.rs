@@ -0,0 +1,8 @@
+use alloy::{network::Network, primitives::B256};
+
+#[derive(Debug, Clone)]
+pub struct EnableTransaction<N: Network> {
+    pub tree: B256,
+    pub root: B256,
+    pub request: N::TransactionRequest,
+}
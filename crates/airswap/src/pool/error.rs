@@ -0,0 +1,101 @@
+use alloy::{primitives::TxHash, providers::PendingTransactionError, transports::TransportError};
+use thiserror::Error;
+
+use crate::{claim::ClaimError, ProtocolVersion, SwapError};
+
+use super::PoolContractErrors;
+
+#[derive(Error, Debug)]
+pub enum PoolError {
+    #[error(transparent)]
+    Contract(alloy::contract::Error),
+    #[error(transparent)]
+    Transport(TransportError),
+    #[error(transparent)]
+    PendingTransaction(#[from] PendingTransactionError),
+    #[error(transparent)]
+    Sol(#[from] alloy::sol_types::Error),
+    #[error(transparent)]
+    Claim(#[from] ClaimError),
+    #[error(transparent)]
+    Events(#[from] SwapError),
+    #[error("The pool reverted with {0:?}")]
+    Revert(PoolContractErrors),
+    #[error("The {0} protocol has no pool on this chain")]
+    PoolNotDeployed(ProtocolVersion),
+    #[error("There is nothing to claim")]
+    NothingToClaim,
+    #[error("The transaction {0} has been reverted")]
+    Reverted(TxHash),
+    #[error("The transaction {0} didn't emit any Withdraw event")]
+    MissingWithdrawEvent(TxHash),
+}
+
+impl PoolError {
+    /// The custom error the pool reverted with, if any.
+    pub fn revert(&self) -> Option<&PoolContractErrors> {
+        match self {
+            PoolError::Revert(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn decode_revert(err: &TransportError) -> Option<PoolContractErrors> {
+    err.as_error_resp()
+        .and_then(|payload| payload.as_decoded_error::<PoolContractErrors>(true))
+}
+
+impl From<TransportError> for PoolError {
+    fn from(err: TransportError) -> Self {
+        match decode_revert(&err) {
+            Some(revert) => PoolError::Revert(revert),
+            None => PoolError::Transport(err),
+        }
+    }
+}
+
+impl From<alloy::contract::Error> for PoolError {
+    fn from(err: alloy::contract::Error) -> Self {
+        match err {
+            alloy::contract::Error::TransportError(err) => err.into(),
+            err => PoolError::Contract(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{b256, hex},
+        rpc::json_rpc::ErrorPayload,
+        sol_types::SolError,
+        transports::TransportError,
+    };
+
+    use crate::pool::{PoolContractErrors, TreeNotEnabled};
+
+    use super::PoolError;
+
+    #[test]
+    fn decode_revert() {
+        let revert = TreeNotEnabled {
+            _0: b256!("2321a10cf12b1095a6c22ac0ba0a150d2e08b60e0f1040e32ec247494ce76ac7"),
+        };
+        let payload = serde_json::from_value::<ErrorPayload>(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": hex::encode_prefixed(revert.abi_encode()),
+        }))
+        .unwrap();
+
+        let err = PoolError::from(alloy::contract::Error::TransportError(
+            TransportError::err_resp(payload),
+        ));
+
+        assert_eq!(
+            err.revert(),
+            Some(&PoolContractErrors::TreeNotEnabled(revert))
+        );
+    }
+}
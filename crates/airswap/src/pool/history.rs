@@ -0,0 +1,53 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, B256},
+    providers::Provider,
+    rpc::types::eth::{BlockNumberOrTag, Filter, Log},
+    sol_types::SolEventInterface,
+    transports::Transport,
+};
+
+use crate::{swap::get_history, EventRecord, SwapError};
+
+use super::{PoolContractEvents, PoolError};
+
+pub type PoolRecord = EventRecord<PoolContractEvents>;
+
+impl TryFrom<&Log> for PoolContractEvents {
+    type Error = SwapError;
+
+    fn try_from(log: &Log) -> Result<Self, Self::Error> {
+        Ok(PoolContractEvents::decode_raw_log(
+            log.topics(),
+            &log.data().data,
+            true,
+        )?)
+    }
+}
+
+/// Every event emitted by the pool, in chain order.
+pub async fn get_pool_history<P, B, T, N>(
+    provider: &P,
+    pool_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Result<Vec<PoolRecord>, PoolError>
+where
+    P: Provider<T, N>,
+    B: Into<BlockNumberOrTag>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let signatures = PoolContractEvents::SELECTORS
+        .iter()
+        .copied()
+        .map(B256::from)
+        .collect::<Vec<_>>();
+    let filter = Filter::new()
+        .address(pool_address)
+        .event_signature(signatures)
+        .from_block(from_block)
+        .to_block(to_block.map(|b| b.into()).unwrap_or_default());
+
+    Ok(get_history(provider, &filter, |log| PoolContractEvents::try_from(log)).await?)
+}
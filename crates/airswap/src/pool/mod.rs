@@ -1,5 +1,54 @@
+#[cfg(feature = "claim")]
+mod admin;
+#[cfg(feature = "claim")]
+pub use admin::PoolAdmin;
+
+#[cfg(feature = "claim")]
+mod client;
+#[cfg(feature = "claim")]
+pub use client::PoolClient;
+
+mod claim_entry;
+pub use claim_entry::ClaimEntry;
+
 mod constants;
+#[allow(deprecated)]
 pub use constants::POLL_ADDRESS;
 
 mod contract;
-pub use contract::PoolContract::*;
+pub use contract::{IPool::Claim, PoolContract::*, IERC20};
+
+mod enable_entry;
+pub use enable_entry::EnableEntry;
+
+mod enable_transaction;
+pub use enable_transaction::EnableTransaction;
+
+#[cfg(feature = "claim")]
+mod error;
+#[cfg(feature = "claim")]
+pub use error::PoolError;
+
+#[cfg(feature = "claim")]
+mod history;
+#[cfg(feature = "claim")]
+pub use history::{get_pool_history, PoolRecord};
+
+#[cfg(feature = "claim")]
+mod pool_ledger;
+#[cfg(feature = "claim")]
+pub use pool_ledger::PoolLedger;
+
+mod reward_estimate;
+pub use reward_estimate::RewardEstimate;
+
+#[cfg(feature = "claim")]
+mod reward_simulator;
+#[cfg(feature = "claim")]
+pub use reward_simulator::RewardSimulator;
+
+mod tree_status;
+pub use tree_status::TreeStatus;
+
+mod withdrawal;
+pub use withdrawal::Withdrawal;
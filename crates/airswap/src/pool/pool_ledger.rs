@@ -0,0 +1,350 @@
+use std::collections::BTreeMap;
+
+use alloy::primitives::{Address, TxHash, B256, U256};
+
+use crate::{claim::ClaimBook, EventRecord};
+
+use super::{ClaimEntry, DrainTo, EnableEntry, PoolContractEvents, PoolRecord};
+
+/// Who claimed what from the pool, rebuilt from its events.
+#[derive(Debug, Clone)]
+pub struct PoolLedger {
+    claim_book: ClaimBook,
+    claims: BTreeMap<(Address, B256), ClaimEntry>,
+    enables: BTreeMap<B256, Vec<EnableEntry>>,
+    scales: Vec<EventRecord<U256>>,
+    maxes: Vec<EventRecord<U256>>,
+    drains: Vec<EventRecord<DrainTo>>,
+    // The claims used by the current transaction, until its Withdraw event is seen. Claims used
+    // without a withdrawal, e.g. by `enableAndSetClaimed`, are dropped with the next transaction.
+    pending: Option<(TxHash, Vec<(Address, B256)>)>,
+}
+
+impl PoolLedger {
+    pub fn new(claim_book: ClaimBook) -> Self {
+        Self {
+            claim_book,
+            claims: BTreeMap::new(),
+            enables: BTreeMap::new(),
+            scales: vec![],
+            maxes: vec![],
+            drains: vec![],
+            pending: None,
+        }
+    }
+
+    pub fn from_records<I>(claim_book: ClaimBook, records: I) -> Self
+    where
+        I: IntoIterator<Item = PoolRecord>,
+    {
+        let mut ledger = Self::new(claim_book);
+
+        for record in records {
+            ledger.add(record);
+        }
+
+        ledger
+    }
+
+    /// Adds an event, the events of a transaction being expected in log order. A claim is
+    /// recorded when used, and its amount once withdrawn in the same transaction.
+    pub fn add(&mut self, record: PoolRecord) {
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|(hash, _)| *hash != record.transaction_hash)
+        {
+            self.pending = None;
+        }
+
+        match &record.event {
+            PoolContractEvents::UseClaim(e) => {
+                self.claims.insert(
+                    (e.account, e.tree),
+                    ClaimEntry {
+                        account: e.account,
+                        tree: e.tree,
+                        token: None,
+                        recipient: None,
+                        value: self.value_of(e.account, &e.tree),
+                        amount: None,
+                        block_number: record.block_number,
+                        timestamp: record.timestamp,
+                        transaction_hash: record.transaction_hash,
+                    },
+                );
+
+                self.pending
+                    .get_or_insert_with(|| (record.transaction_hash, vec![]))
+                    .1
+                    .push((e.account, e.tree));
+            }
+            PoolContractEvents::Withdraw(e) => {
+                let used = self
+                    .pending
+                    .take()
+                    .map(|(_, used)| used)
+                    .unwrap_or_default();
+
+                for key in used.iter().filter(|(account, _)| *account == e.account) {
+                    let Some(claim) = self.claims.get_mut(key) else {
+                        continue;
+                    };
+
+                    claim.token = Some(e.token);
+                    claim.recipient = Some(e.recipient);
+                    claim.amount = claim
+                        .value
+                        .filter(|_| !e.value.is_zero())
+                        .map(|value| e.amount * value / e.value);
+                }
+            }
+            PoolContractEvents::Enable(e) => {
+                self.enables.entry(e.tree).or_default().push(EnableEntry {
+                    tree: e.tree,
+                    root: e.root,
+                    block_number: record.block_number,
+                    timestamp: record.timestamp,
+                    transaction_hash: record.transaction_hash,
+                });
+            }
+            PoolContractEvents::SetScale(e) => {
+                let scale = e.scale;
+                self.scales.push(record.map(|_| scale));
+            }
+            PoolContractEvents::SetMax(e) => {
+                let max = e.max;
+                self.maxes.push(record.map(|_| max));
+            }
+            PoolContractEvents::DrainTo(e) => {
+                let e = e.clone();
+                self.drains.push(record.map(|_| e));
+            }
+            _ => {}
+        }
+    }
+
+    // The points of the vote, zero-point votes included like in `ClaimBook::get_claim`
+    fn value_of(&self, account: Address, tree: &B256) -> Option<U256> {
+        self.claim_book
+            .get_proposal(tree)?
+            .votes
+            .iter()
+            .find(|v| v.address == account)
+            .map(|v| v.points)
+    }
+
+    pub fn claims(&self) -> impl Iterator<Item = &ClaimEntry> {
+        self.claims.values()
+    }
+
+    pub fn get_claim(&self, account: Address, tree: &B256) -> Option<&ClaimEntry> {
+        self.claims.get(&(account, *tree))
+    }
+
+    pub fn is_claimed(&self, account: Address, tree: &B256) -> bool {
+        self.claims.contains_key(&(account, *tree))
+    }
+
+    /// The claims of the account, ordered by tree.
+    pub fn account_claims(&self, account: Address) -> Vec<&ClaimEntry> {
+        self.claims
+            .range((account, B256::ZERO)..=(account, B256::repeat_byte(0xff)))
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
+    pub fn tree_claims(&self, tree: &B256) -> Vec<&ClaimEntry> {
+        self.claims().filter(|c| c.tree == *tree).collect()
+    }
+
+    /// The amount of `token` withdrawn through the trees with a known value.
+    pub fn total_claimed(&self, token: Address) -> U256 {
+        self.claims()
+            .filter(|c| c.token == Some(token))
+            .filter_map(|c| c.amount)
+            .sum()
+    }
+
+    pub fn enable_history(&self, tree: &B256) -> &[EnableEntry] {
+        self.enables.get(tree).map_or(&[], |e| e.as_slice())
+    }
+
+    /// The last root enabled for every tree.
+    pub fn enabled_roots(&self) -> BTreeMap<B256, B256> {
+        self.enables
+            .iter()
+            .filter_map(|(tree, entries)| entries.last().map(|e| (*tree, e.root)))
+            .collect()
+    }
+
+    pub fn scale_history(&self) -> &[EventRecord<U256>] {
+        &self.scales
+    }
+
+    pub fn max_history(&self) -> &[EventRecord<U256>] {
+        &self.maxes
+    }
+
+    pub fn drains(&self) -> &[EventRecord<DrainTo>] {
+        &self.drains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, b256, Address, TxHash, B256, U256};
+
+    use crate::{
+        claim::ClaimBook,
+        pool::{Enable, PoolContractEvents, PoolRecord, UseClaim, Withdraw},
+        EventRecord,
+    };
+
+    use super::PoolLedger;
+
+    fn record(event: PoolContractEvents, transaction: u8, log_index: u64) -> PoolRecord {
+        EventRecord {
+            event,
+            address: Address::ZERO,
+            block_number: 100,
+            block_hash: B256::ZERO,
+            timestamp: Some(1700000000),
+            transaction_hash: TxHash::repeat_byte(transaction),
+            transaction_index: 0,
+            log_index,
+        }
+    }
+
+    #[test]
+    fn ledger() {
+        let claim_book = ClaimBook::embedded();
+        let account = address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff");
+        let tree = b256!("2321a10cf12b1095a6c22ac0ba0a150d2e08b60e0f1040e32ec247494ce76ac7");
+        let unknown_tree = B256::repeat_byte(1);
+        let value = claim_book.get_claim(account, &tree).unwrap().unwrap().value;
+        let token = Address::repeat_byte(2);
+
+        let records = [
+            record(
+                PoolContractEvents::Enable(Enable {
+                    tree,
+                    root: B256::repeat_byte(3),
+                }),
+                1,
+                0,
+            ),
+            record(
+                PoolContractEvents::UseClaim(UseClaim { account, tree }),
+                2,
+                1,
+            ),
+            record(
+                PoolContractEvents::UseClaim(UseClaim {
+                    account,
+                    tree: unknown_tree,
+                }),
+                2,
+                2,
+            ),
+            record(
+                PoolContractEvents::Withdraw(Withdraw {
+                    account,
+                    recipient: account,
+                    token,
+                    value: value * U256::from(2),
+                    amount: U256::from(1000),
+                }),
+                2,
+                3,
+            ),
+        ];
+
+        let ledger = PoolLedger::from_records(claim_book, records);
+
+        let claim = ledger.get_claim(account, &tree).unwrap();
+        assert_eq!(claim.value, Some(value));
+        assert_eq!(claim.token, Some(token));
+        assert_eq!(claim.amount, Some(U256::from(500)));
+        assert_eq!(
+            ledger.get_claim(account, &unknown_tree).unwrap().amount,
+            None
+        );
+        assert_eq!(ledger.account_claims(account).len(), 2);
+        assert_eq!(ledger.total_claimed(token), U256::from(500));
+        assert_eq!(ledger.enable_history(&tree).len(), 1);
+        assert_eq!(ledger.enabled_roots()[&tree], B256::repeat_byte(3));
+    }
+
+    #[test]
+    fn enable_and_set_claimed() {
+        let claim_book = ClaimBook::embedded();
+        let account = address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff");
+        let tree = b256!("2321a10cf12b1095a6c22ac0ba0a150d2e08b60e0f1040e32ec247494ce76ac7");
+        let value = claim_book.get_claim(account, &tree).unwrap().unwrap().value;
+
+        // `enableAndSetClaimed` marks the claim as used without withdrawing
+        let records = [
+            record(
+                PoolContractEvents::UseClaim(UseClaim { account, tree }),
+                1,
+                0,
+            ),
+            record(
+                PoolContractEvents::Enable(Enable {
+                    tree,
+                    root: B256::repeat_byte(3),
+                }),
+                1,
+                1,
+            ),
+        ];
+
+        let ledger = PoolLedger::from_records(claim_book, records);
+
+        assert!(ledger.is_claimed(account, &tree));
+
+        let claim = ledger.get_claim(account, &tree).unwrap();
+        assert_eq!(claim.value, Some(value));
+        assert_eq!(claim.token, None);
+        assert_eq!(claim.recipient, None);
+        assert_eq!(claim.amount, None);
+        assert_eq!(ledger.total_claimed(Address::repeat_byte(2)), U256::ZERO);
+        assert_eq!(ledger.enabled_roots()[&tree], B256::repeat_byte(3));
+        assert!(ledger.pending.is_some());
+    }
+
+    #[test]
+    fn claims_used_without_withdrawal() {
+        let claim_book = ClaimBook::embedded();
+        let account = address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff");
+        let tree = b256!("2321a10cf12b1095a6c22ac0ba0a150d2e08b60e0f1040e32ec247494ce76ac7");
+        let token = Address::repeat_byte(2);
+
+        let records = [
+            record(
+                PoolContractEvents::UseClaim(UseClaim { account, tree }),
+                1,
+                0,
+            ),
+            // Another transaction withdrawing without claims of its own
+            record(
+                PoolContractEvents::Withdraw(Withdraw {
+                    account,
+                    recipient: account,
+                    token,
+                    value: U256::from(1),
+                    amount: U256::from(1000),
+                }),
+                2,
+                1,
+            ),
+        ];
+
+        let ledger = PoolLedger::from_records(claim_book, records);
+
+        assert!(ledger.pending.is_none());
+        assert_eq!(ledger.get_claim(account, &tree).unwrap().token, None);
+        assert_eq!(ledger.total_claimed(token), U256::ZERO);
+    }
+}
@@ -0,0 +1,12 @@
+use std::collections::BTreeMap;
+
+use alloy::primitives::{Address, B256, U256};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardEstimate {
+    pub account: Address,
+    pub trees: Vec<B256>,
+    // The sum of the points of the account in the trees
+    pub value: U256,
+    pub amounts: BTreeMap<Address, U256>,
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use alloy::primitives::{Address, U256};
+
+use crate::claim::Proposals;
+
+use super::RewardEstimate;
+
+const MAX_DIVISOR: u64 = 100;
+
+/// Estimates withdrawals offline with the same formula as the pool's `calculate`:
+/// `max * value * balance / (10 ** scale + value) / 100`.
+#[derive(Debug, Clone)]
+pub struct RewardSimulator {
+    scale: U256,
+    max: U256,
+    balances: BTreeMap<Address, U256>,
+}
+
+impl RewardSimulator {
+    pub fn new(scale: U256, max: U256) -> Self {
+        Self {
+            scale,
+            max,
+            balances: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_balance(mut self, token: Address, balance: U256) -> Self {
+        self.balances.insert(token, balance);
+        self
+    }
+
+    pub fn scale(&self) -> U256 {
+        self.scale
+    }
+
+    pub fn max(&self) -> U256 {
+        self.max
+    }
+
+    pub fn balances(&self) -> &BTreeMap<Address, U256> {
+        &self.balances
+    }
+
+    /// The amount the pool would send for `value` points out of `balance`, `None` where the
+    /// pool would revert on overflow.
+    pub fn calculate(&self, value: U256, balance: U256) -> Option<U256> {
+        let divisor = U256::from(10).checked_pow(self.scale)?.checked_add(value)?;
+
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let amount = self.max.checked_mul(value)?.checked_mul(balance)? / divisor;
+
+        Some(amount / U256::from(MAX_DIVISOR))
+    }
+
+    /// The payout of `value` points for every known token balance.
+    pub fn payout(&self, value: U256) -> BTreeMap<Address, U256> {
+        self.balances
+            .iter()
+            .filter_map(|(token, balance)| Some((*token, self.calculate(value, *balance)?)))
+            .collect()
+    }
+
+    /// The payout of every voter withdrawing all their claims at once, each estimated
+    /// against the current balances as if they were the first to withdraw.
+    pub fn simulate(&self, proposals: &Proposals) -> Vec<RewardEstimate> {
+        let mut values = BTreeMap::<Address, (Vec<_>, U256)>::new();
+
+        for (tree, proposal) in proposals.iter() {
+            for vote in &proposal.votes {
+                let (trees, value) = values.entry(vote.address).or_default();
+                trees.push(*tree);
+                *value += vote.points;
+            }
+        }
+
+        values
+            .into_iter()
+            .map(|(account, (trees, value))| RewardEstimate {
+                account,
+                trees,
+                value,
+                amounts: self.payout(value),
+            })
+            .collect()
+    }
+
+    pub fn simulate_account(&self, proposals: &Proposals, account: Address) -> RewardEstimate {
+        let mut trees = vec![];
+        let mut value = U256::ZERO;
+
+        for (tree, proposal) in proposals.iter() {
+            if let Some(vote) = proposal.votes.iter().find(|v| v.address == account) {
+                trees.push(*tree);
+                value += vote.points;
+            }
+        }
+
+        RewardEstimate {
+            account,
+            trees,
+            value,
+            amounts: self.payout(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, Address, U256};
+
+    use crate::claim::Proposals;
+
+    use super::RewardSimulator;
+
+    #[test]
+    fn calculate() {
+        let simulator = RewardSimulator::new(U256::from(4), U256::from(50));
+
+        // 50 * 10000 * 1000000 / (10000 + 10000) / 100
+        assert_eq!(
+            simulator.calculate(U256::from(10000), U256::from(1000000)),
+            Some(U256::from(250000))
+        );
+        assert_eq!(simulator.calculate(U256::MAX, U256::from(2)), None);
+        assert_eq!(
+            RewardSimulator::new(U256::from(78), U256::from(1)).calculate(U256::ZERO, U256::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn simulate() {
+        let token = Address::repeat_byte(1);
+        let account = address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff");
+        let proposals = Proposals::embedded();
+        let simulator = RewardSimulator::new(U256::from(18), U256::from(100))
+            .with_balance(token, U256::from(10).pow(U256::from(24)));
+
+        let estimates = simulator.simulate(&proposals);
+        let estimate = estimates.iter().find(|e| e.account == account).unwrap();
+
+        assert_eq!(estimate, &simulator.simulate_account(&proposals, account));
+        assert_eq!(
+            estimate.amounts[&token],
+            simulator
+                .calculate(estimate.value, U256::from(10).pow(U256::from(24)))
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use alloy::primitives::B256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStatus {
+    pub tree: B256,
+    // The root computed from the proposal votes
+    pub root: B256,
+    // The root returned by `rootsByTree`, zero if the tree isn't enabled
+    pub enabled_root: B256,
+}
+
+impl TreeStatus {
+    pub fn is_enabled(&self) -> bool {
+        !self.enabled_root.is_zero()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.is_enabled()
+    }
+
+    /// The tree has been enabled with another root than the one of its votes.
+    pub fn is_conflicting(&self) -> bool {
+        self.is_enabled() && self.enabled_root != self.root
+    }
+}
@@ -0,0 +1,10 @@
+use alloy::primitives::TxHash;
+
+use super::{UseClaim, Withdraw};
+
+#[derive(Debug, Clone)]
+pub struct Withdrawal {
+    pub transaction_hash: TxHash,
+    pub withdraw: Withdraw,
+    pub use_claims: Vec<UseClaim>,
+}
@@ -0,0 +1,63 @@
+use std::{future::Future, time::Duration};
+
+use tokio::time::sleep;
+
+use super::RegistryError;
+
+/// How the batch registry calls are run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchOptions {
+    /// The maximum number of calls in flight.
+    pub concurrency: usize,
+    /// How many times a call failing on a retryable error is tried again.
+    pub retries: usize,
+    /// The delay before the first retry, doubled on each following one.
+    pub retry_delay: Duration,
+}
+
+impl BatchOptions {
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    pub(crate) async fn retry<F, Fut, T>(&self, mut f: F) -> Result<T, RegistryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RegistryError>>,
+    {
+        let mut delay = self.retry_delay;
+
+        for _ in 0..self.retries {
+            match f().await {
+                Err(err) if err.is_retryable() => {
+                    sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+
+        f().await
+    }
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            retries: 2,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
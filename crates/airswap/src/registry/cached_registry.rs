@@ -0,0 +1,531 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, SystemTime},
+};
+
+use alloy::primitives::Address;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+    time::sleep,
+};
+use tracing::warn;
+
+use crate::Maker;
+
+use super::{RegistryCacheKey, RegistryContract, RegistryError};
+
+// How long the cache waits for other changes before being written
+const PERSIST_DELAY: Duration = Duration::from_secs(1);
+
+/// Keeps the results of another registry, until they are older than the TTL or
+/// [`CachedRegistry::invalidate`] is called.
+pub struct CachedRegistry<R> {
+    inner: R,
+    ttl: Option<Duration>,
+    persistence: Option<Arc<Persistence>>,
+    persist_delay: Duration,
+    state: Arc<RwLock<CacheState>>,
+}
+
+struct Persistence {
+    path: PathBuf,
+    key: RegistryCacheKey,
+    // A write is scheduled, the changes made until then being written with it
+    scheduled: AtomicBool,
+    // Writes one snapshot at a time, so that the last one written is the latest
+    write_lock: Mutex<()>,
+}
+
+impl Persistence {
+    async fn write(&self, state: &RwLock<CacheState>) {
+        let _guard = self.write_lock.lock().await;
+        let persisted = PersistedCache {
+            key: self.key,
+            state: state.read().await.clone(),
+        };
+        let path = self.path.clone();
+
+        let result = match serde_json::to_vec(&persisted) {
+            Ok(bytes) => tokio::task::spawn_blocking(move || write_atomically(&path, &bytes))
+                .await
+                .unwrap_or_else(|err| Err(std::io::Error::other(err)))
+                .map_err(RegistryError::from),
+            Err(err) => Err(err.into()),
+        };
+
+        if let Err(err) = result {
+            warn!(
+                "Can't persist the registry cache to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+// Writes a temporary file then renames it, so that a crash never leaves a partial cache. The
+// temporary name is unique, other processes or registries may write the same cache.
+fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+
+    let temporary = path.with_extension(format!(
+        "{}.{}.tmp",
+        std::process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    fs::write(&temporary, bytes)?;
+    fs::rename(temporary, path)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheState {
+    makers: Option<Cached<Vec<Maker>>>,
+    // The makers looked up one by one, when the makers aren't cached
+    #[serde(default)]
+    maker_lookups: BTreeMap<Address, Cached<Maker>>,
+    tokens: BTreeMap<Address, Cached<Vec<Address>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Cached<V> {
+    value: V,
+    fetched_at: SystemTime,
+}
+
+impl<V: Clone> Cached<V> {
+    fn new(value: V) -> Self {
+        Self {
+            value,
+            fetched_at: SystemTime::now(),
+        }
+    }
+
+    fn fresh(&self, ttl: Option<Duration>) -> Option<V> {
+        let expired = ttl.is_some_and(|ttl| {
+            self.fetched_at
+                .elapsed()
+                .map_or(true, |elapsed| elapsed > ttl)
+        });
+
+        (!expired).then(|| self.value.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedCache {
+    key: RegistryCacheKey,
+    #[serde(flatten)]
+    state: CacheState,
+}
+
+impl<R> CachedRegistry<R> {
+    /// Caches the results until they are invalidated.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            ttl: None,
+            persistence: None,
+            persist_delay: PERSIST_DELAY,
+            state: Default::default(),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Persists the cache in `directory`, loading what a previous run left for the same
+    /// registry. A corrupt cache is ignored. The changes are written a second after the
+    /// first one, see [`CachedRegistry::with_persist_delay`].
+    pub fn with_persistence<P: AsRef<Path>>(
+        mut self,
+        directory: P,
+        key: RegistryCacheKey,
+    ) -> Result<Self, RegistryError> {
+        let path = directory.as_ref().join(key.file_name());
+
+        if path.exists() {
+            match serde_json::from_slice::<PersistedCache>(&fs::read(&path)?) {
+                Ok(persisted) if persisted.key == key => {
+                    self.state = Arc::new(RwLock::new(persisted.state));
+                }
+                Ok(_) => {}
+                Err(err) => warn!(
+                    "Ignoring the corrupt registry cache {}: {err}",
+                    path.display()
+                ),
+            }
+        } else {
+            fs::create_dir_all(directory)?;
+        }
+
+        self.persistence = Some(Arc::new(Persistence {
+            path,
+            key,
+            scheduled: AtomicBool::new(false),
+            write_lock: Mutex::new(()),
+        }));
+
+        Ok(self)
+    }
+
+    /// How long the changes are gathered before being written, when persisted.
+    pub fn with_persist_delay(mut self, delay: Duration) -> Self {
+        self.persist_delay = delay;
+        self
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    pub async fn invalidate(&self) {
+        *self.state.write().await = CacheState::default();
+        self.flush().await;
+    }
+
+    /// Writes the cache now, e.g. before exiting, instead of waiting for the scheduled write.
+    pub async fn flush(&self) {
+        if let Some(persistence) = &self.persistence {
+            persistence.write(&self.state).await;
+        }
+    }
+
+    // Writes the cache after the persist delay, along with the changes made until then
+    fn schedule_persist(&self) {
+        let Some(persistence) = &self.persistence else {
+            return;
+        };
+
+        if persistence.scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let persistence = persistence.clone();
+        let state = self.state.clone();
+        let delay = self.persist_delay;
+
+        tokio::spawn(async move {
+            sleep(delay).await;
+            persistence.scheduled.store(false, Ordering::SeqCst);
+            persistence.write(&state).await;
+        });
+    }
+}
+
+impl<R: RegistryContract> CachedRegistry<R> {
+    /// Fetches again the makers and the tokens of the cached makers, keeping the previous
+    /// tokens of the makers failing.
+    pub async fn refresh(&self) -> Result<(), RegistryError> {
+        let makers = self.inner.get_makers().await?;
+        let cached_tokens = self
+            .state
+            .read()
+            .await
+            .tokens
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        let mut tokens = BTreeMap::new();
+
+        for maker in cached_tokens {
+            match self.inner.get_tokens(maker).await {
+                Ok(maker_tokens) => {
+                    tokens.insert(maker, Cached::new(maker_tokens));
+                }
+                Err(err) => warn!("Can't refresh the tokens of {maker}: {err}"),
+            }
+        }
+
+        {
+            let mut state = self.state.write().await;
+            state.makers = Some(Cached::new(makers));
+            state.maker_lookups.clear();
+            state.tokens.extend(tokens);
+        }
+        self.flush().await;
+
+        Ok(())
+    }
+}
+
+impl<R: RegistryContract + 'static> CachedRegistry<R> {
+    /// Refreshes the cache every `interval`, until the registry is dropped.
+    pub fn spawn_refresh(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let registry: Weak<Self> = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+
+                let Some(registry) = registry.upgrade() else {
+                    return;
+                };
+
+                if let Err(err) = registry.refresh().await {
+                    warn!("Can't refresh the registry cache: {err}");
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl<R: RegistryContract> RegistryContract for CachedRegistry<R> {
+    async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+        {
+            let state = self.state.read().await;
+
+            // The last URL set by the maker wins
+            if let Some(maker) = state
+                .makers
+                .as_ref()
+                .and_then(|m| m.fresh(self.ttl))
+                .and_then(|makers| makers.into_iter().rev().find(|m| m.address == address))
+                .or_else(|| {
+                    state
+                        .maker_lookups
+                        .get(&address)
+                        .and_then(|m| m.fresh(self.ttl))
+                })
+            {
+                return Ok(maker);
+            }
+        }
+
+        let maker = self.inner.get_maker(address).await?;
+        self.state
+            .write()
+            .await
+            .maker_lookups
+            .insert(address, Cached::new(maker.clone()));
+        self.schedule_persist();
+
+        Ok(maker)
+    }
+
+    async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+        if let Some(makers) = self
+            .state
+            .read()
+            .await
+            .makers
+            .as_ref()
+            .and_then(|m| m.fresh(self.ttl))
+        {
+            return Ok(makers);
+        }
+
+        let makers = self.inner.get_makers().await?;
+        self.state.write().await.makers = Some(Cached::new(makers.clone()));
+        self.schedule_persist();
+
+        Ok(makers)
+    }
+
+    async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
+        if let Some(tokens) = self
+            .state
+            .read()
+            .await
+            .tokens
+            .get(&maker_address)
+            .and_then(|t| t.fresh(self.ttl))
+        {
+            return Ok(tokens);
+        }
+
+        let tokens = self.inner.get_tokens(maker_address).await?;
+        self.state
+            .write()
+            .await
+            .tokens
+            .insert(maker_address, Cached::new(tokens.clone()));
+        self.schedule_persist();
+
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use alloy::primitives::Address;
+    use async_trait::async_trait;
+
+    use crate::{
+        registry::{RegistryCacheKey, RegistryContract, RegistryError},
+        Config, Maker,
+    };
+
+    use super::CachedRegistry;
+
+    #[derive(Default)]
+    struct CountingRegistry {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RegistryContract for CountingRegistry {
+        async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Maker::new(address, String::new()))
+        }
+
+        async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![Maker::new(Address::ZERO, String::new())])
+        }
+
+        async fn get_tokens(&self, _: Address) -> Result<Vec<Address>, RegistryError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![])
+        }
+    }
+
+    // Fails for the maker 0x0101... once `fail` is set, the token of the first call being 0x00...
+    // and the next ones 0x0101...
+    #[derive(Default)]
+    struct FailingRegistry {
+        fail: AtomicBool,
+        calls: AtomicU8,
+    }
+
+    #[async_trait]
+    impl RegistryContract for FailingRegistry {
+        async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+            Ok(Maker::new(address, String::new()))
+        }
+
+        async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+            Ok(vec![])
+        }
+
+        async fn get_tokens(&self, maker: Address) -> Result<Vec<Address>, RegistryError> {
+            if self.fail.load(Ordering::SeqCst) && maker == Address::repeat_byte(1) {
+                return Err(RegistryError::MakerNotFound(maker));
+            }
+
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![Address::repeat_byte(calls.min(1))])
+        }
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let registry = CachedRegistry::new(CountingRegistry::default());
+
+        registry.get_makers().await.unwrap();
+        registry.get_makers().await.unwrap();
+        registry.get_tokens(Address::ZERO).await.unwrap();
+        registry.get_tokens(Address::ZERO).await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate().await;
+        registry.get_makers().await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn maker_lookups() {
+        let registry = CachedRegistry::new(CountingRegistry::default());
+        let address = Address::repeat_byte(1);
+
+        registry.get_maker(address).await.unwrap();
+        registry.get_maker(address).await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 1);
+
+        // Found in the makers once they are cached
+        registry.get_makers().await.unwrap();
+        registry.get_maker(Address::ZERO).await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate().await;
+        registry.get_maker(address).await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn ttl() {
+        let registry =
+            CachedRegistry::new(CountingRegistry::default()).with_ttl(Duration::from_millis(20));
+
+        registry.get_makers().await.unwrap();
+        registry.get_makers().await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        registry.get_makers().await.unwrap();
+        assert_eq!(registry.inner().calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn persistence() {
+        let directory = std::env::temp_dir().join(format!("airswap-cache-{}", std::process::id()));
+        let key = RegistryCacheKey::from(&Config::mainnet_v5());
+
+        let registry = CachedRegistry::new(CountingRegistry::default())
+            .with_persistence(&directory, key)
+            .unwrap()
+            .with_persist_delay(Duration::from_millis(20));
+        registry.get_makers().await.unwrap();
+        registry.get_tokens(Address::ZERO).await.unwrap();
+
+        // Both changes are written once the delay is over
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let reloaded = CachedRegistry::new(CountingRegistry::default())
+            .with_persistence(&directory, key)
+            .unwrap();
+        assert_eq!(reloaded.get_makers().await.unwrap().len(), 1);
+        reloaded.get_tokens(Address::ZERO).await.unwrap();
+        assert_eq!(reloaded.inner().calls.load(Ordering::SeqCst), 0);
+
+        // A corrupt cache is ignored
+        std::fs::write(directory.join(key.file_name()), b"{").unwrap();
+
+        let corrupt = CachedRegistry::new(CountingRegistry::default())
+            .with_persistence(&directory, key)
+            .unwrap();
+        corrupt.get_makers().await.unwrap();
+        assert_eq!(corrupt.inner().calls.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_failing_makers() {
+        let failing = Address::repeat_byte(1);
+        let registry = CachedRegistry::new(FailingRegistry::default());
+
+        registry.get_tokens(Address::ZERO).await.unwrap();
+        registry.get_tokens(failing).await.unwrap();
+
+        registry.inner().fail.store(true, Ordering::SeqCst);
+        registry.refresh().await.unwrap();
+
+        // Fetching it again would fail
+        assert_eq!(
+            registry.get_tokens(failing).await.unwrap(),
+            vec![Address::repeat_byte(1)]
+        );
+        assert_eq!(
+            registry.get_tokens(Address::ZERO).await.unwrap(),
+            vec![Address::repeat_byte(1)]
+        );
+    }
+}
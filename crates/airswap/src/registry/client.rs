@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 use alloy::primitives::Address;
 use alloy::sol_types::{SolCall, SolEvent};
@@ -7,25 +7,38 @@ use alloy::{
     providers::Provider,
     rpc::types::eth::Filter,
     sol,
-    transports::{Transport, TransportError},
+    transports::Transport,
 };
 use async_trait::async_trait;
-use futures::{future::try_join_all, TryFutureExt};
-use thiserror::Error;
+use futures::{stream, StreamExt};
+use tracing::warn;
 
-use crate::{Config, Maker, MakerWithSupportedTokens, ProtocolVersion};
+use crate::{
+    BatchOptions, CachedRegistry, Config, Maker, MakerBatch, MakerUrl, MakerWithSupportedTokens,
+    ProtocolVersion,
+};
+
+use super::RegistryError;
 
 sol!(LegacyRegistryContract, "abi/registry.json");
-sol!(RegistryV4Contract, "abi/registry_v4.json");
+sol!(
+    #[derive(Debug, PartialEq, Eq)]
+    RegistryV4Contract,
+    "abi/registry_v4.json"
+);
 
 #[async_trait]
-pub trait RegistryContract {
+pub trait RegistryContract: Send + Sync {
     async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError>;
     async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError>;
     async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError>;
 }
 
-async fn call<P, T, N, C>(provider: &P, call: C, to: Address) -> Result<C::Return, RegistryError>
+pub(super) async fn call<P, T, N, C>(
+    provider: &P,
+    call: C,
+    to: Address,
+) -> Result<C::Return, RegistryError>
 where
     P: Provider<T, N>,
     T: Transport + Clone,
@@ -36,10 +49,25 @@ where
         .with_input(call.abi_encode())
         .with_to(to);
 
-    let result = provider.call(&tx).await?;
-    let decoded = C::abi_decode_returns(&result, true)?;
+    let result = provider
+        .call(&tx)
+        .await
+        .map_err(|err| RegistryError::from(err).with_call(C::SIGNATURE, to))?;
+
+    // Calling an address without code succeeds with no data
+    if result.is_empty() {
+        let code = provider
+            .get_code_at(to)
+            .await
+            .map_err(|err| RegistryError::from(err).with_call(C::SIGNATURE, to))?;
+
+        if code.is_empty() {
+            return Err(RegistryError::ContractNotFound(to).with_call(C::SIGNATURE, to));
+        }
+    }
 
-    Ok(decoded)
+    C::abi_decode_returns(&result, true)
+        .map_err(|err| RegistryError::from(err).with_call(C::SIGNATURE, to))
 }
 
 async fn get_makers_events<P, T, N, E>(
@@ -57,57 +85,88 @@ where
         .address(config.registry_address)
         .event(E::SIGNATURE);
 
-    let set_url_events = provider.get_logs(&filter).await?;
+    let context = |err: RegistryError| err.with_call(E::SIGNATURE, config.registry_address);
+    let set_url_events = provider
+        .get_logs(&filter)
+        .await
+        .map_err(|err| context(err.into()))?;
 
     set_url_events
         .into_iter()
-        .map(|log| E::decode_log_data(log.data(), true).map_err(RegistryError::from))
+        .map(|log| E::decode_log_data(log.data(), true).map_err(|err| context(err.into())))
         .collect::<Result<Vec<_>, _>>()
 }
 
-fn normalized_maker(account: Address, mut url: String) -> Maker {
-    if url.contains("wintermute") {
-        //continue;
+/// Normalizes the registered URL, keeping the raw one when it's invalid so that the maker
+/// can still be listed, see [`Maker::is_valid`].
+fn normalized_maker(account: Address, url: String) -> Maker {
+    match MakerUrl::parse(&url) {
+        Ok(url) => Maker::new(account, url.to_string()),
+        Err(err) => {
+            warn!("Maker {account} registered an invalid URL: {err}");
+            Maker::new(account, url)
+        }
     }
+}
 
-    if url.starts_with("wss://") {
-        //continue;
+#[async_trait]
+impl<R: RegistryContract + ?Sized> RegistryContract for Arc<R> {
+    async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+        (**self).get_maker(address).await
     }
 
-    //if !url.starts_with("http") {
-    //url = format!("https://{}", url);
-    //}
-
-    url = url.replace('\"', "");
+    async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+        (**self).get_makers().await
+    }
 
-    Maker::new(account, url)
+    async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
+        (**self).get_tokens(maker_address).await
+    }
 }
 
-pub enum RegistryClient<P, T, N> {
-    Legacy(LegacyRegistry<P, T, N>),
-    V4(RegistryV4<P, T, N>),
+/// Reads makers from any registry, on-chain or not.
+#[derive(Clone)]
+pub struct RegistryClient {
+    contract: Arc<dyn RegistryContract>,
 }
 
-impl<P, T, N> RegistryClient<P, T, N>
-where
-    P: Provider<T, N>,
-    N: Network,
-    T: Transport + Clone,
-{
-    pub fn new(provider: P, config: Config) -> Self {
+impl RegistryClient {
+    /// The on-chain registry of the config.
+    pub fn new<P, T, N>(provider: P, config: Config) -> Self
+    where
+        P: Provider<T, N> + 'static,
+        T: Transport + Clone + Send + Sync,
+        N: Network + Send + Sync,
+    {
         match config.protocol_version {
-            ProtocolVersion::Legacy => Self::Legacy(LegacyRegistry::new(provider, config)),
-            _ => Self::V4(RegistryV4::new(provider, config)),
+            ProtocolVersion::Legacy => Self::from_contract(LegacyRegistry::new(provider, config)),
+            _ => Self::from_contract(RegistryV4::new(provider, config)),
         }
     }
 
-    pub async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
-        match self {
-            RegistryClient::Legacy(registry) => registry.get_maker(address).await,
-            RegistryClient::V4(registry) => registry.get_maker(address).await,
+    /// The on-chain registry of the config, its results being kept for `ttl`.
+    pub fn cached<P, T, N>(provider: P, config: Config, ttl: Duration) -> Self
+    where
+        P: Provider<T, N> + 'static,
+        T: Transport + Clone + Send + Sync,
+        N: Network + Send + Sync,
+    {
+        Self::from_contract(CachedRegistry::new(Self::new(provider, config)).with_ttl(ttl))
+    }
+
+    pub fn from_contract<R>(contract: R) -> Self
+    where
+        R: RegistryContract + 'static,
+    {
+        Self {
+            contract: Arc::new(contract),
         }
     }
 
+    pub async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+        self.contract.get_maker(address).await
+    }
+
     pub async fn get_maker_with_supported_tokens(
         &self,
         address: Address,
@@ -119,30 +178,72 @@ where
     }
 
     pub async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
-        match self {
-            RegistryClient::Legacy(registry) => registry.get_makers().await,
-            RegistryClient::V4(registry) => registry.get_makers().await,
-        }
+        self.contract.get_makers().await
     }
 
     pub async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
-        match self {
-            RegistryClient::Legacy(registry) => registry.get_tokens(maker_address).await,
-            RegistryClient::V4(registry) => registry.get_tokens(maker_address).await,
-        }
+        self.contract.get_tokens(maker_address).await
     }
 
     pub async fn get_makers_with_supported_tokens(
         &self,
     ) -> Result<Vec<MakerWithSupportedTokens>, RegistryError> {
-        let futures = self.get_makers().await?.into_iter().map(|m| {
-            self.get_tokens(m.address)
-                .map_ok(|supported_tokens| MakerWithSupportedTokens::new(m, supported_tokens))
-        });
+        self.get_makers_with_supported_tokens_batch(&BatchOptions::default())
+            .await?
+            .into_result()
+    }
+
+    /// The tokens of every maker, a failing maker not failing the others. Only listing the
+    /// makers can fail the whole batch.
+    pub async fn get_makers_with_supported_tokens_batch(
+        &self,
+        options: &BatchOptions,
+    ) -> Result<MakerBatch, RegistryError> {
+        let makers = options.retry(|| self.get_makers()).await?;
+        let addresses = makers.iter().map(|m| m.address).collect::<Vec<_>>();
+        let results = self.get_tokens_batch(addresses, options).await;
+
+        Ok(MakerBatch::new(
+            makers
+                .into_iter()
+                .zip(results)
+                .map(|(maker, (_, tokens))| (maker, tokens))
+                .collect(),
+        ))
+    }
+
+    /// The tokens of each maker, in the order of `makers`.
+    pub async fn get_tokens_batch<I>(
+        &self,
+        makers: I,
+        options: &BatchOptions,
+    ) -> Vec<(Address, Result<Vec<Address>, RegistryError>)>
+    where
+        I: IntoIterator<Item = Address>,
+    {
+        stream::iter(makers)
+            .map(|maker| async move {
+                let tokens = options.retry(|| self.get_tokens(maker)).await;
+                (maker, tokens)
+            })
+            .buffered(options.concurrency.max(1))
+            .collect()
+            .await
+    }
+}
+
+#[async_trait]
+impl RegistryContract for RegistryClient {
+    async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+        self.contract.get_maker(address).await
+    }
 
-        let makers_with_supported_tokens = try_join_all(futures).await?;
+    async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+        self.contract.get_makers().await
+    }
 
-        Ok(makers_with_supported_tokens)
+    async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
+        self.contract.get_tokens(maker_address).await
     }
 }
 
@@ -177,7 +278,7 @@ where
         )
         .await?;
 
-        Ok(Maker::new(address, url._0))
+        Ok(normalized_maker(address, url._0))
     }
 
     async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
@@ -236,7 +337,7 @@ where
         )
         .await?;
 
-        Ok(Maker::new(address, url._0))
+        Ok(normalized_maker(address, url._0))
     }
 
     async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
@@ -263,13 +364,3 @@ where
         Ok(x.tokenList)
     }
 }
-
-#[derive(Error, Debug)]
-pub enum RegistryError {
-    #[error(transparent)]
-    Transport(#[from] TransportError),
-    #[error("Invalid log")]
-    Log,
-    #[error(transparent)]
-    Sol(#[from] alloy::sol_types::Error),
-}
@@ -0,0 +1,107 @@
+use alloy::{
+    network::Network,
+    primitives::{address, keccak256, Address, B256},
+    providers::Provider,
+    sol,
+    transports::Transport,
+};
+
+use super::{client::call, RegistryError};
+
+sol! {
+    interface IENSRegistry {
+        function resolver(bytes32 node) external view returns (address);
+    }
+
+    interface IENSResolver {
+        function name(bytes32 node) external view returns (string);
+        function addr(bytes32 node) external view returns (address);
+    }
+}
+
+/// The ENS registry, at the same address on mainnet and its testnets.
+pub const ENS_REGISTRY_ADDRESS: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+pub fn namehash(name: &str) -> B256 {
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            keccak256([node.as_slice(), keccak256(label.as_bytes()).as_slice()].concat())
+        })
+}
+
+/// The primary ENS name of the address, if it resolves back to the address.
+pub async fn lookup_ens_name<P, T, N>(
+    provider: &P,
+    address: Address,
+) -> Result<Option<String>, RegistryError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let reverse_node = namehash(&format!("{:x}.addr.reverse", address));
+    let Some(name) = resolve(provider, reverse_node, |node| IENSResolver::nameCall {
+        node,
+    })
+    .await?
+    .map(|r| r._0)
+    .filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+
+    // Anyone can claim any name in the reverse record
+    let forward = resolve(provider, namehash(&name), |node| IENSResolver::addrCall {
+        node,
+    })
+    .await?
+    .map(|r| r._0);
+
+    Ok((forward == Some(address)).then_some(name))
+}
+
+async fn resolve<P, T, N, C>(
+    provider: &P,
+    node: B256,
+    call_for: impl FnOnce(B256) -> C,
+) -> Result<Option<C::Return>, RegistryError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    C: alloy::sol_types::SolCall + Send + Sync,
+{
+    let resolver = call(
+        provider,
+        IENSRegistry::resolverCall { node },
+        ENS_REGISTRY_ADDRESS,
+    )
+    .await?
+    ._0;
+
+    if resolver == Address::ZERO {
+        return Ok(None);
+    }
+
+    Ok(Some(call(provider, call_for(node), resolver).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{b256, B256};
+
+    use super::namehash;
+
+    #[test]
+    fn namehash_vectors() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use alloy::{
+    primitives::Address,
+    transports::{RpcError, TransportError, TransportErrorKind},
+};
+use thiserror::Error;
+
+use super::RegistryV4ContractErrors;
+
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error(transparent)]
+    Transport(TransportError),
+    #[error("Rate limited by the RPC: {0}")]
+    RateLimited(TransportError),
+    #[error(transparent)]
+    Sol(#[from] alloy::sol_types::Error),
+    #[error("The registry reverted with {0:?}")]
+    Revert(RegistryV4ContractErrors),
+    #[error("There is no contract at {0}")]
+    ContractNotFound(Address),
+    #[error("{call} on {address} failed: {source}")]
+    Call {
+        call: &'static str,
+        address: Address,
+        source: Box<RegistryError>,
+    },
+    #[error("The maker {0} isn't registered")]
+    MakerNotFound(Address),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+impl RegistryError {
+    /// Adds the call, e.g. a function or event signature, and the contract that failed.
+    pub fn with_call(self, call: &'static str, address: Address) -> Self {
+        RegistryError::Call {
+            call,
+            address,
+            source: Box::new(self),
+        }
+    }
+
+    /// The error without its call context.
+    pub fn root(&self) -> &RegistryError {
+        match self {
+            RegistryError::Call { source, .. } => source.root(),
+            err => err,
+        }
+    }
+
+    /// The custom error the registry reverted with, if any.
+    pub fn revert(&self) -> Option<&RegistryV4ContractErrors> {
+        match self.root() {
+            RegistryError::Revert(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.root(), RegistryError::RateLimited(_))
+    }
+
+    pub fn is_contract_missing(&self) -> bool {
+        matches!(self.root(), RegistryError::ContractNotFound(_))
+    }
+
+    /// Whether trying again may succeed: the request didn't reach the node, or was rate
+    /// limited. An error response, e.g. a failed execution, would be returned again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.root(),
+            RegistryError::Transport(RpcError::Transport(_)) | RegistryError::RateLimited(_)
+        )
+    }
+}
+
+fn decode_revert(err: &TransportError) -> Option<RegistryV4ContractErrors> {
+    err.as_error_resp()
+        .and_then(|payload| payload.as_decoded_error::<RegistryV4ContractErrors>(true))
+}
+
+fn is_rate_limit(err: &TransportError) -> bool {
+    match err {
+        RpcError::Transport(TransportErrorKind::HttpError(err)) => err.is_rate_limit_err(),
+        RpcError::ErrorResp(payload) => {
+            // 429 and Infura's -32005 "limit exceeded"
+            matches!(payload.code, 429 | -32005)
+                || payload.message.to_lowercase().contains("rate limit")
+        }
+        _ => false,
+    }
+}
+
+impl From<TransportError> for RegistryError {
+    fn from(err: TransportError) -> Self {
+        if let Some(revert) = decode_revert(&err) {
+            RegistryError::Revert(revert)
+        } else if is_rate_limit(&err) {
+            RegistryError::RateLimited(err)
+        } else {
+            RegistryError::Transport(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{address, hex, Address},
+        rpc::json_rpc::ErrorPayload,
+        sol_types::SolError,
+        transports::{TransportError, TransportErrorKind},
+    };
+
+    use crate::registry::{RegistryV4Contract::TokenExists, RegistryV4ContractErrors};
+
+    use super::RegistryError;
+
+    fn payload(value: serde_json::Value) -> TransportError {
+        TransportError::err_resp(serde_json::from_value::<ErrorPayload>(value).unwrap())
+    }
+
+    #[test]
+    fn decode_revert() {
+        let revert = TokenExists {
+            _0: address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+        };
+        let err = RegistryError::from(payload(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": hex::encode_prefixed(revert.abi_encode()),
+        })))
+        .with_call("addTokens(address[])", Address::ZERO);
+
+        assert_eq!(
+            err.revert(),
+            Some(&RegistryV4ContractErrors::TokenExists(revert))
+        );
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn rate_limit() {
+        let err = RegistryError::from(payload(serde_json::json!({
+            "code": -32005,
+            "message": "daily request count exceeded, request rate limited",
+        })));
+        assert!(err.is_rate_limited());
+        assert!(err.is_retryable());
+
+        let err = RegistryError::from(TransportError::Transport(TransportErrorKind::HttpError(
+            alloy::transports::HttpError {
+                status: 429,
+                body: String::new(),
+            },
+        )));
+        assert!(err.is_rate_limited());
+
+        assert!(err.is_retryable());
+
+        let err = RegistryError::ContractNotFound(Address::ZERO)
+            .with_call("getServerURLs(address[])", Address::ZERO);
+        assert!(err.is_contract_missing());
+        assert!(!err.is_rate_limited());
+    }
+
+    #[test]
+    fn error_response_not_retryable() {
+        let err = RegistryError::from(payload(serde_json::json!({
+            "code": -32000,
+            "message": "execution reverted",
+        })))
+        .with_call("getTokensForStaker(address)", Address::ZERO);
+        assert!(!err.is_retryable());
+
+        let err = RegistryError::from(TransportError::Transport(TransportErrorKind::BackendGone));
+        assert!(err.is_retryable());
+    }
+}
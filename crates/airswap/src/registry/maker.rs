@@ -1,10 +1,13 @@
 use std::{fmt::Display, sync::Arc};
 
 use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
 
-use crate::{registry::KNOWN_MAKERS, MakerWithSupportedTokens};
+use crate::MakerWithSupportedTokens;
 
-#[derive(Debug, Clone)]
+use super::{maker_name, MakerTransport, MakerUrl, MakerUrlError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Maker {
     pub address: Address,
     pub url: String,
@@ -14,6 +17,19 @@ impl Maker {
     pub fn new(address: Address, url: String) -> Self {
         Self { address, url }
     }
+
+    pub fn parse_url(&self) -> Result<MakerUrl, MakerUrlError> {
+        MakerUrl::parse(&self.url)
+    }
+
+    /// Whether the registered URL can be reached, see [`MakerUrl::parse`].
+    pub fn is_valid(&self) -> bool {
+        self.parse_url().is_ok()
+    }
+
+    pub fn transport(&self) -> Option<MakerTransport> {
+        self.parse_url().ok().map(|url| url.transport())
+    }
 }
 
 impl From<Arc<MakerWithSupportedTokens>> for Maker {
@@ -24,11 +40,6 @@ impl From<Arc<MakerWithSupportedTokens>> for Maker {
 
 impl Display for Maker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let maker_name = KNOWN_MAKERS
-            .get(&self.address)
-            .cloned()
-            .unwrap_or(format!("{}", self.address));
-
-        write!(f, "{maker_name}")
+        write!(f, "{}", maker_name(&self.address))
     }
 }
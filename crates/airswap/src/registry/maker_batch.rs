@@ -0,0 +1,144 @@
+use alloy::primitives::Address;
+
+use crate::{Maker, MakerWithSupportedTokens};
+
+use super::RegistryError;
+
+/// The makers of a registry with, for each of them, their tokens or why they couldn't be
+/// fetched.
+#[derive(Debug, Default)]
+pub struct MakerBatch {
+    pub results: Vec<(Maker, Result<Vec<Address>, RegistryError>)>,
+}
+
+impl MakerBatch {
+    pub fn new(results: Vec<(Maker, Result<Vec<Address>, RegistryError>)>) -> Self {
+        Self { results }
+    }
+
+    /// Whether the tokens of every maker have been fetched.
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|(_, r)| r.is_ok())
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    pub fn makers(&self) -> Vec<MakerWithSupportedTokens> {
+        self.results
+            .iter()
+            .filter_map(|(maker, result)| {
+                let tokens = result.as_ref().ok()?;
+                Some(MakerWithSupportedTokens::new(maker.clone(), tokens.clone()))
+            })
+            .collect()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (&Maker, &RegistryError)> {
+        self.results
+            .iter()
+            .filter_map(|(maker, result)| Some((maker, result.as_ref().err()?)))
+    }
+
+    /// The makers whose tokens have been fetched, dropping the failures.
+    pub fn into_makers(self) -> Vec<MakerWithSupportedTokens> {
+        self.results
+            .into_iter()
+            .filter_map(|(maker, result)| Some(MakerWithSupportedTokens::new(maker, result.ok()?)))
+            .collect()
+    }
+
+    /// The makers, or the first failure.
+    pub fn into_result(self) -> Result<Vec<MakerWithSupportedTokens>, RegistryError> {
+        self.results
+            .into_iter()
+            .map(|(maker, result)| Ok(MakerWithSupportedTokens::new(maker, result?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use alloy::{
+        primitives::{address, Address},
+        transports::TransportErrorKind,
+    };
+    use async_trait::async_trait;
+
+    use crate::{
+        registry::{BatchOptions, RegistryContract, RegistryError},
+        Maker, RegistryClient,
+    };
+
+    const FLAKY: Address = address!("0101010101010101010101010101010101010101");
+    const BROKEN: Address = address!("0202020202020202020202020202020202020202");
+
+    #[derive(Default)]
+    struct FailingRegistry {
+        flaky_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl RegistryContract for FailingRegistry {
+        async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+            Err(RegistryError::MakerNotFound(address))
+        }
+
+        async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+            Ok([FLAKY, BROKEN, Address::ZERO]
+                .into_iter()
+                .map(|a| Maker::new(a, String::new()))
+                .collect())
+        }
+
+        async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
+            match maker_address {
+                // Fails once, then succeeds
+                FLAKY if self.flaky_calls.fetch_add(1, Ordering::SeqCst) == 0 => {
+                    Err(TransportErrorKind::custom_str("Connection reset").into())
+                }
+                BROKEN => Err(RegistryError::MakerNotFound(maker_address)),
+                _ => Ok(vec![Address::ZERO]),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn partial_failure() {
+        let client = RegistryClient::from_contract(FailingRegistry::default());
+        let options = BatchOptions::default()
+            .with_concurrency(2)
+            .with_retry_delay(Duration::from_millis(1));
+
+        let batch = client
+            .get_makers_with_supported_tokens_batch(&options)
+            .await
+            .unwrap();
+
+        assert_eq!(batch.len(), 3);
+        assert!(!batch.is_complete());
+        assert_eq!(
+            batch
+                .makers()
+                .iter()
+                .map(|m| *m.address())
+                .collect::<Vec<_>>(),
+            vec![FLAKY, Address::ZERO]
+        );
+        assert_eq!(
+            batch.failures().map(|(m, _)| m.address).collect::<Vec<_>>(),
+            vec![BROKEN]
+        );
+        assert!(batch.into_result().is_err());
+    }
+}
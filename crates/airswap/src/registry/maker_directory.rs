@@ -0,0 +1,249 @@
+use std::{collections::HashMap, fs::File, future::Future, io::Read, path::Path, sync::RwLock};
+
+use alloy::{
+    network::Network,
+    primitives::{address, Address},
+    providers::Provider,
+    transports::Transport,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::{lookup_ens_name, MakerInfo, RegistryError};
+
+static MAKER_DIRECTORY: Lazy<RwLock<MakerDirectory>> =
+    Lazy::new(|| RwLock::new(MakerDirectory::builtin()));
+
+/// The names of the builtin makers.
+#[deprecated(note = "use `maker_name` or `MakerDirectory::builtin` instead")]
+pub static KNOWN_MAKERS: Lazy<HashMap<Address, String>> = Lazy::new(|| {
+    MakerDirectory::builtin()
+        .makers
+        .into_iter()
+        .map(|(address, info)| (address, info.name))
+        .collect()
+});
+
+/// Names and metadata of makers, e.g. loaded from a JSON object keyed by address:
+/// `{ "0x...": { "name": ..., "logo": ..., "contact": ..., "notes": ... } }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MakerDirectory {
+    makers: HashMap<Address, MakerInfo>,
+}
+
+impl MakerDirectory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The makers known by this crate.
+    pub fn builtin() -> Self {
+        [
+            (
+                address!("143395428158a57d17bcd8899770460656de98e4"),
+                "MyEth",
+            ),
+            (address!("111bb8c3542f2b92fb41b8d913c01d3788431111"), "B2C2"),
+            (
+                address!("bb289bc97591f70d8216462df40ed713011b968a"),
+                "Alphalab",
+            ),
+            (
+                address!("e0d90babe0081cf34328270620cd127eab8073db"),
+                "Altono",
+            ),
+            (address!("0F4A4B5A9935544190a6eAf34ec5A343738D4166"), "N"),
+        ]
+        .into_iter()
+        .map(|(address, name)| (address, MakerInfo::new(name)))
+        .collect()
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, RegistryError> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, RegistryError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, RegistryError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    pub fn insert(&mut self, address: Address, info: MakerInfo) -> Option<MakerInfo> {
+        self.makers.insert(address, info)
+    }
+
+    /// Adds the makers of `other`, replacing the ones already known.
+    pub fn merge(&mut self, other: MakerDirectory) {
+        self.makers.extend(other.makers);
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&MakerInfo> {
+        self.makers.get(address)
+    }
+
+    /// The name of the maker, or its address when unknown.
+    pub fn name(&self, address: &Address) -> String {
+        self.get(address)
+            .map_or_else(|| address.to_string(), |info| info.name.clone())
+    }
+
+    pub fn makers(&self) -> &HashMap<Address, MakerInfo> {
+        &self.makers
+    }
+
+    /// Names the unknown makers after their primary ENS name, returning how many were found.
+    /// The addresses whose lookup fails are left unresolved.
+    pub async fn resolve_ens<P, T, N, I>(&mut self, provider: &P, addresses: I) -> usize
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+        I: IntoIterator<Item = Address>,
+    {
+        self.resolve_names(addresses, |address| lookup_ens_name(provider, address))
+            .await
+    }
+
+    async fn resolve_names<I, F, Fut>(&mut self, addresses: I, mut lookup: F) -> usize
+    where
+        I: IntoIterator<Item = Address>,
+        F: FnMut(Address) -> Fut,
+        Fut: Future<Output = Result<Option<String>, RegistryError>>,
+    {
+        let mut resolved = 0;
+
+        for address in addresses {
+            if self.makers.contains_key(&address) {
+                continue;
+            }
+
+            match lookup(address).await {
+                Ok(Some(name)) => {
+                    let info = MakerInfo {
+                        ens: Some(name.clone()),
+                        ..MakerInfo::new(name)
+                    };
+
+                    self.makers.insert(address, info);
+                    resolved += 1;
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Can't look up the ENS name of {address}: {err}"),
+            }
+        }
+
+        resolved
+    }
+}
+
+impl FromIterator<(Address, MakerInfo)> for MakerDirectory {
+    fn from_iter<I: IntoIterator<Item = (Address, MakerInfo)>>(iter: I) -> Self {
+        Self {
+            makers: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Adds or replaces a maker in the directory used to display makers.
+pub fn register_maker_info(address: Address, info: MakerInfo) {
+    MAKER_DIRECTORY.write().unwrap().insert(address, info);
+}
+
+/// Adds the makers of `directory` to the directory used to display makers.
+pub fn load_maker_directory(directory: MakerDirectory) {
+    MAKER_DIRECTORY.write().unwrap().merge(directory);
+}
+
+pub fn maker_info(address: &Address) -> Option<MakerInfo> {
+    MAKER_DIRECTORY.read().unwrap().get(address).cloned()
+}
+
+pub fn maker_name(address: &Address) -> String {
+    MAKER_DIRECTORY.read().unwrap().name(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{address, Address};
+
+    use crate::Maker;
+
+    use super::{maker_name, register_maker_info, MakerDirectory, MakerInfo};
+    use crate::registry::RegistryError;
+
+    #[test]
+    fn load() {
+        let directory = MakerDirectory::from_json_str(
+            r#"{
+                "0x0101010101010101010101010101010101010101": {
+                    "name": "Maker",
+                    "logo": "https://maker.example.com/logo.png",
+                    "contact": "ops@maker.example.com"
+                }
+            }"#,
+        )
+        .unwrap();
+        let address = address!("0101010101010101010101010101010101010101");
+
+        assert_eq!(
+            directory.get(&address),
+            Some(
+                &MakerInfo::new("Maker")
+                    .with_logo("https://maker.example.com/logo.png")
+                    .with_contact("ops@maker.example.com")
+            )
+        );
+        assert_eq!(directory.name(&Address::ZERO), Address::ZERO.to_string());
+    }
+
+    #[test]
+    fn register() {
+        let address = address!("0202020202020202020202020202020202020202");
+        let maker = Maker::new(address, String::new());
+
+        assert_eq!(maker.to_string(), address.to_string());
+
+        register_maker_info(address, MakerInfo::new("Registered"));
+
+        assert_eq!(maker_name(&address), "Registered");
+        assert_eq!(maker.to_string(), "Registered");
+    }
+
+    #[tokio::test]
+    async fn resolve_names() {
+        let failing = Address::repeat_byte(1);
+        let named = Address::repeat_byte(2);
+        let mut directory = MakerDirectory::new();
+
+        let resolved = directory
+            .resolve_names([failing, named, Address::ZERO], |address| async move {
+                match address {
+                    a if a == failing => Err(RegistryError::MakerNotFound(a)),
+                    a if a == named => Ok(Some(String::from("maker.eth"))),
+                    _ => Ok(None),
+                }
+            })
+            .await;
+
+        assert_eq!(resolved, 1);
+        assert_eq!(directory.name(&named), "maker.eth");
+        assert_eq!(
+            directory.get(&named).unwrap().ens.as_deref(),
+            Some("maker.eth")
+        );
+        assert!(directory.get(&failing).is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn known_makers() {
+        let address = address!("111bb8c3542f2b92fb41b8d913c01d3788431111");
+
+        assert_eq!(super::KNOWN_MAKERS[&address], "B2C2");
+    }
+}
@@ -0,0 +1,24 @@
+use alloy::primitives::Address;
+
+use crate::{ProtocolVersion, VersionedMaker};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MakerRegistration {
+    pub protocol_version: ProtocolVersion,
+    pub registry_address: Address,
+    pub swap_address: Address,
+    pub url: String,
+    pub supported_tokens: Vec<Address>,
+}
+
+impl From<&VersionedMaker> for MakerRegistration {
+    fn from(value: &VersionedMaker) -> Self {
+        Self {
+            protocol_version: value.config.protocol_version,
+            registry_address: value.config.registry_address,
+            swap_address: value.config.swap_address,
+            url: value.maker.url(),
+            supported_tokens: value.maker.supported_tokens.clone(),
+        }
+    }
+}
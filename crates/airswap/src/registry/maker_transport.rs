@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MakerTransport {
+    Https,
+    Http,
+    Wss,
+    Ws,
+}
+
+impl MakerTransport {
+    pub fn from_scheme(scheme: &str) -> Option<Self> {
+        match scheme {
+            "https" => Some(Self::Https),
+            "http" => Some(Self::Http),
+            "wss" => Some(Self::Wss),
+            "ws" => Some(Self::Ws),
+            _ => None,
+        }
+    }
+
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Https => "https",
+            Self::Http => "http",
+            Self::Wss => "wss",
+            Self::Ws => "ws",
+        }
+    }
+
+    /// Whether JSON-RPC requests can be posted to the maker.
+    pub fn is_http(&self) -> bool {
+        matches!(self, Self::Https | Self::Http)
+    }
+
+    pub fn is_websocket(&self) -> bool {
+        matches!(self, Self::Wss | Self::Ws)
+    }
+}
+
+impl Display for MakerTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.scheme())
+    }
+}
@@ -0,0 +1,171 @@
+use std::{fmt::Display, net::IpAddr, str::FromStr};
+
+use thiserror::Error;
+use url::{Host, Url};
+
+use super::MakerTransport;
+
+/// A validated maker URL, see [`MakerUrl::parse`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MakerUrl {
+    url: Url,
+    transport: MakerTransport,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MakerUrlError {
+    #[error("Empty URL")]
+    Empty,
+    #[error("Invalid URL {0}: {1}")]
+    Invalid(String, url::ParseError),
+    #[error("Unsupported scheme {0}")]
+    UnsupportedScheme(String),
+    #[error("Missing host in {0}")]
+    MissingHost(String),
+    #[error("Can't guess the scheme of {0}")]
+    AmbiguousScheme(String),
+}
+
+impl MakerUrl {
+    /// Parses the URL registered by a maker.
+    ///
+    /// Quotes, whitespace and trailing punctuation are dropped, and bare domain names are
+    /// assumed to be served over HTTPS. Local and IP hosts without a scheme are rejected, as
+    /// they are as likely to be served over HTTP.
+    pub fn parse(raw: &str) -> Result<Self, MakerUrlError> {
+        let cleaned = raw
+            .replace(['"', '\''], "")
+            .trim()
+            .trim_end_matches([',', ';', '.'])
+            .to_string();
+
+        if cleaned.is_empty() {
+            return Err(MakerUrlError::Empty);
+        }
+
+        let with_scheme = if cleaned.contains("://") {
+            cleaned
+        } else {
+            let host = cleaned
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or_default()
+                .rsplit_once(':')
+                .map_or(cleaned.as_str(), |(host, _)| host);
+
+            if host.eq_ignore_ascii_case("localhost") || IpAddr::from_str(host).is_ok() {
+                return Err(MakerUrlError::AmbiguousScheme(cleaned));
+            }
+
+            format!("https://{cleaned}")
+        };
+
+        let url = Url::parse(&with_scheme)
+            .map_err(|err| MakerUrlError::Invalid(with_scheme.clone(), err))?;
+        let transport = MakerTransport::from_scheme(url.scheme())
+            .ok_or_else(|| MakerUrlError::UnsupportedScheme(url.scheme().to_string()))?;
+
+        match url.host() {
+            None => return Err(MakerUrlError::MissingHost(with_scheme)),
+            // A bare word is rather a typo than a host
+            Some(Host::Domain(domain)) if !domain.contains('.') && domain != "localhost" => {
+                return Err(MakerUrlError::MissingHost(with_scheme))
+            }
+            _ => {}
+        }
+
+        Ok(Self { url, transport })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn transport(&self) -> MakerTransport {
+        self.transport
+    }
+
+    /// The URL, without the trailing slash of an empty path.
+    pub fn as_str(&self) -> &str {
+        let url = self.url.as_str();
+
+        if self.url.path() == "/" && self.url.query().is_none() && self.url.fragment().is_none() {
+            url.trim_end_matches('/')
+        } else {
+            url
+        }
+    }
+}
+
+impl FromStr for MakerUrl {
+    type Err = MakerUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for MakerUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::MakerTransport;
+
+    use super::{MakerUrl, MakerUrlError};
+
+    #[test]
+    fn normalize() {
+        let cases = [
+            ("https://maker.example.com", "https://maker.example.com"),
+            ("https://maker.example.com/", "https://maker.example.com"),
+            ("\"https://maker.example.com\"", "https://maker.example.com"),
+            (" maker.example.com/rpc, ", "https://maker.example.com/rpc"),
+            ("maker.example.com:8080", "https://maker.example.com:8080"),
+            (
+                "HTTP://Maker.Example.com/rpc",
+                "http://maker.example.com/rpc",
+            ),
+            ("wss://maker.example.com/ws", "wss://maker.example.com/ws"),
+        ];
+
+        for (raw, expected) in cases {
+            assert_eq!(MakerUrl::parse(raw).unwrap().as_str(), expected, "{raw}");
+        }
+
+        assert_eq!(
+            MakerUrl::parse("wss://maker.example.com")
+                .unwrap()
+                .transport(),
+            MakerTransport::Wss
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(MakerUrl::parse(" \"\" "), Err(MakerUrlError::Empty));
+        assert!(matches!(
+            MakerUrl::parse("ftp://maker.example.com"),
+            Err(MakerUrlError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(
+            MakerUrl::parse("localhost:3000"),
+            Err(MakerUrlError::AmbiguousScheme(_))
+        ));
+        assert!(matches!(
+            MakerUrl::parse("127.0.0.1:3000"),
+            Err(MakerUrlError::AmbiguousScheme(_))
+        ));
+        assert!(matches!(
+            MakerUrl::parse("not a url"),
+            Err(MakerUrlError::Invalid(..))
+        ));
+        assert!(matches!(
+            MakerUrl::parse("test"),
+            Err(MakerUrlError::MissingHost(_))
+        ));
+    }
+}
@@ -1,12 +1,15 @@
 use std::fmt::Display;
 
 use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
 
 use crate::Maker;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MakerWithSupportedTokens {
+    #[serde(flatten)]
     pub maker: Maker,
+    #[serde(default)]
     pub supported_tokens: Vec<Address>,
 }
 
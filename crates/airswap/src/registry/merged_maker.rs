@@ -0,0 +1,189 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use alloy::primitives::Address;
+
+use crate::{Maker, ProtocolVersion, VersionedMaker};
+
+use super::MakerRegistration;
+
+/// A maker as registered across the registries of every protocol version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedMaker {
+    pub address: Address,
+    // Ordered by protocol version, oldest first
+    pub registrations: Vec<MakerRegistration>,
+}
+
+impl MergedMaker {
+    /// The registration of the most recent version.
+    pub fn latest(&self) -> &MakerRegistration {
+        self.registrations
+            .last()
+            .expect("A merged maker has at least one registration")
+    }
+
+    pub fn url(&self) -> &str {
+        &self.latest().url
+    }
+
+    pub fn maker(&self) -> Maker {
+        Maker::new(self.address, self.url().to_string())
+    }
+
+    pub fn versions(&self) -> Vec<ProtocolVersion> {
+        self.registrations
+            .iter()
+            .map(|r| r.protocol_version)
+            .collect()
+    }
+
+    pub fn supports(&self, protocol_version: ProtocolVersion) -> bool {
+        self.registration(protocol_version).is_some()
+    }
+
+    pub fn registration(&self, protocol_version: ProtocolVersion) -> Option<&MakerRegistration> {
+        self.registrations
+            .iter()
+            .find(|r| r.protocol_version == protocol_version)
+    }
+
+    pub fn swap_addresses(&self) -> Vec<Address> {
+        self.registrations.iter().map(|r| r.swap_address).collect()
+    }
+
+    /// The distinct URLs, ignoring case and trailing slashes.
+    pub fn urls(&self) -> Vec<&str> {
+        let mut urls: Vec<&str> = vec![];
+
+        for registration in &self.registrations {
+            if !urls.iter().any(|url| same_url(url, &registration.url)) {
+                urls.push(&registration.url);
+            }
+        }
+
+        urls
+    }
+
+    /// The maker registered different URLs in different versions.
+    pub fn has_conflicting_urls(&self) -> bool {
+        self.urls().len() > 1
+    }
+
+    /// The tokens supported in any version.
+    pub fn supported_tokens(&self) -> Vec<Address> {
+        let mut tokens = self
+            .registrations
+            .iter()
+            .flat_map(|r| r.supported_tokens.iter().copied())
+            .collect::<Vec<_>>();
+
+        tokens.sort();
+        tokens.dedup();
+        tokens
+    }
+}
+
+impl Display for MergedMaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let versions = self
+            .versions()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{} ({versions})", self.maker())
+    }
+}
+
+fn same_url(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/')
+        .eq_ignore_ascii_case(b.trim_end_matches('/'))
+}
+
+/// Merges the makers of several registries by address, ordered by address, each version of a
+/// maker being the one of its config.
+pub fn merge_makers<I>(makers: I) -> Vec<MergedMaker>
+where
+    I: IntoIterator<Item = VersionedMaker>,
+{
+    let mut merged = BTreeMap::<Address, Vec<MakerRegistration>>::new();
+
+    for maker in makers {
+        let registrations = merged.entry(*maker.maker.address()).or_default();
+        let registration = MakerRegistration::from(&maker);
+
+        // A maker can set its URL several times in the same registry, the last one wins
+        match registrations
+            .iter_mut()
+            .find(|r| r.protocol_version == registration.protocol_version)
+        {
+            Some(existing) => *existing = registration,
+            None => registrations.push(registration),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(address, mut registrations)| {
+            registrations.sort_by_key(|r| {
+                ProtocolVersion::ALL
+                    .iter()
+                    .position(|v| *v == r.protocol_version)
+            });
+
+            MergedMaker {
+                address,
+                registrations,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use crate::{Config, Maker, MakerWithSupportedTokens, ProtocolVersion, VersionedMaker};
+
+    use super::merge_makers;
+
+    fn maker(address: Address, url: &str, version: ProtocolVersion) -> VersionedMaker {
+        VersionedMaker::new(
+            MakerWithSupportedTokens::new(
+                Maker::new(address, url.to_string()),
+                vec![Address::repeat_byte(0xaa)],
+            ),
+            Config::try_new(1, version).unwrap(),
+        )
+    }
+
+    #[test]
+    fn merge() {
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+
+        let merged = merge_makers([
+            maker(a, "https://maker.a/", ProtocolVersion::V5),
+            maker(b, "https://maker.b", ProtocolVersion::V4),
+            maker(a, "https://MAKER.a", ProtocolVersion::Legacy),
+            maker(b, "https://other.b", ProtocolVersion::V5),
+        ]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged[0].versions(),
+            vec![ProtocolVersion::Legacy, ProtocolVersion::V5]
+        );
+        assert!(!merged[0].has_conflicting_urls());
+        assert!(merged[1].has_conflicting_urls());
+        assert_eq!(merged[1].url(), "https://other.b");
+        assert_eq!(
+            merged[1].swap_addresses(),
+            vec![
+                Config::mainnet_v4().swap_address,
+                Config::mainnet_v5().swap_address
+            ]
+        );
+    }
+}
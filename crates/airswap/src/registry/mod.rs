@@ -1,36 +1,44 @@
+mod batch_options;
+mod cached_registry;
 mod client;
+mod ens;
+mod error;
 mod maker;
+mod maker_batch;
+mod maker_directory;
+mod maker_info;
+mod maker_registration;
+mod maker_transport;
+mod maker_url;
 mod maker_with_supported_tokens;
+mod merged_maker;
+mod multi_client;
+mod registry_cache_key;
+mod static_registry;
+mod versioned_maker;
 
-use std::collections::HashMap;
-
-use alloy::primitives::{address, Address};
-pub use client::{RegistryClient, RegistryError};
+pub use batch_options::BatchOptions;
+pub use cached_registry::CachedRegistry;
+pub use client::{
+    LegacyRegistry, RegistryClient, RegistryContract, RegistryV4,
+    RegistryV4Contract::{self, RegistryV4ContractErrors},
+};
+pub use ens::{lookup_ens_name, namehash, ENS_REGISTRY_ADDRESS};
+pub use error::RegistryError;
 pub use maker::Maker;
+pub use maker_batch::MakerBatch;
+#[allow(deprecated)]
+pub use maker_directory::KNOWN_MAKERS;
+pub use maker_directory::{
+    load_maker_directory, maker_info, maker_name, register_maker_info, MakerDirectory,
+};
+pub use maker_info::MakerInfo;
+pub use maker_registration::MakerRegistration;
+pub use maker_transport::MakerTransport;
+pub use maker_url::{MakerUrl, MakerUrlError};
 pub use maker_with_supported_tokens::MakerWithSupportedTokens;
-use once_cell::sync::Lazy;
-
-pub static KNOWN_MAKERS: Lazy<HashMap<Address, String>> = Lazy::new(|| {
-    HashMap::from([
-        (
-            address!("143395428158a57d17bcd8899770460656de98e4"),
-            String::from("MyEth"),
-        ),
-        (
-            address!("111bb8c3542f2b92fb41b8d913c01d3788431111"),
-            String::from("B2C2"),
-        ),
-        (
-            address!("bb289bc97591f70d8216462df40ed713011b968a"),
-            String::from("Alphalab"),
-        ),
-        (
-            address!("e0d90babe0081cf34328270620cd127eab8073db"),
-            String::from("Altono"),
-        ),
-        (
-            address!("0F4A4B5A9935544190a6eAf34ec5A343738D4166"),
-            String::from("N"),
-        ),
-    ])
-});
+pub use merged_maker::{merge_makers, MergedMaker};
+pub use multi_client::MultiRegistryClient;
+pub use registry_cache_key::RegistryCacheKey;
+pub use static_registry::StaticRegistry;
+pub use versioned_maker::VersionedMaker;
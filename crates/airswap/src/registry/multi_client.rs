@@ -0,0 +1,315 @@
+use alloy::{network::Network, providers::Provider, transports::Transport};
+use futures::{
+    future::{join_all, try_join_all},
+    stream, StreamExt,
+};
+use tracing::warn;
+
+use crate::{
+    chain_configs, detect_deployments, json_rpc::Protocol, Config, ConfigError, Maker, MakerClient,
+    MakerWithSupportedTokens, ProtocolVersion, RegistryClient,
+};
+
+use super::{merge_makers, BatchOptions, MakerBatch, MergedMaker, RegistryError, VersionedMaker};
+
+// How many makers are asked for their protocols at once
+const PROTOCOLS_CONCURRENCY: usize = 8;
+
+/// Queries the registries of several protocol versions, labeling the makers by version.
+#[derive(Clone)]
+pub struct MultiRegistryClient {
+    // Each registry once, with the configs of the versions sharing it, oldest first
+    registries: Vec<(Vec<Config>, RegistryClient)>,
+}
+
+impl MultiRegistryClient {
+    /// The versions sharing a registry, as V4 and V5 do, query it once.
+    pub fn new<P, T, N>(provider: P, configs: Vec<Config>) -> Self
+    where
+        P: Provider<T, N> + Clone + 'static,
+        T: Transport + Clone + Send + Sync,
+        N: Network + Send + Sync,
+    {
+        let registries = group_by_registry(configs)
+            .into_iter()
+            .map(|configs| {
+                let registry = RegistryClient::new(provider.clone(), configs[0].clone());
+                (configs, registry)
+            })
+            .collect();
+
+        Self { registries }
+    }
+
+    /// Registries of any kind, e.g. static ones, labeled by the config of their version.
+    pub fn from_registries(registries: Vec<(Config, RegistryClient)>) -> Self {
+        Self {
+            registries: registries
+                .into_iter()
+                .map(|(config, registry)| (vec![config], registry))
+                .collect(),
+        }
+    }
+
+    /// Every version known to be deployed on the chain.
+    pub fn for_chain<P, T, N>(provider: P, chain_id: u64) -> Self
+    where
+        P: Provider<T, N> + Clone + 'static,
+        T: Transport + Clone + Send + Sync,
+        N: Network + Send + Sync,
+    {
+        Self::new(provider, chain_configs(chain_id))
+    }
+
+    /// The versions actually deployed on the chain of the provider.
+    pub async fn detect<P, T, N>(provider: P) -> Result<Self, ConfigError>
+    where
+        P: Provider<T, N> + Clone + 'static,
+        T: Transport + Clone + Send + Sync,
+        N: Network + Send + Sync,
+    {
+        let configs = detect_deployments(&provider)
+            .await?
+            .into_iter()
+            .map(|d| d.config)
+            .collect();
+
+        Ok(Self::new(provider, configs))
+    }
+
+    pub fn configs(&self) -> impl Iterator<Item = &Config> {
+        self.registries.iter().flat_map(|(configs, _)| configs)
+    }
+
+    pub fn registry(&self, protocol_version: ProtocolVersion) -> Option<&RegistryClient> {
+        self.registries
+            .iter()
+            .find(|(configs, _)| {
+                configs
+                    .iter()
+                    .any(|c| c.protocol_version == protocol_version)
+            })
+            .map(|(_, registry)| registry)
+    }
+
+    /// The makers of each registry, labeled by the versions they quote for, see
+    /// [`MultiRegistryClient::get_makers_with_supported_tokens`].
+    pub async fn get_makers(&self) -> Result<Vec<(ProtocolVersion, Maker)>, RegistryError> {
+        let makers = try_join_all(
+            self.registries
+                .iter()
+                .map(|(configs, registry)| async move {
+                    let makers = registry
+                        .get_makers()
+                        .await?
+                        .into_iter()
+                        .map(|m| MakerWithSupportedTokens::new(m, vec![]));
+
+                    Ok::<_, RegistryError>(versioned_makers(makers, configs).await)
+                }),
+        )
+        .await?;
+
+        Ok(makers
+            .into_iter()
+            .flatten()
+            .map(|m| (m.protocol_version(), m.maker.maker))
+            .collect())
+    }
+
+    /// The makers of each registry, once per version they quote for. When versions share the
+    /// registry, these are the ones whose swap contract the maker lists in `getProtocols`.
+    pub async fn get_makers_with_supported_tokens(
+        &self,
+    ) -> Result<Vec<VersionedMaker>, RegistryError> {
+        let makers = try_join_all(
+            self.registries
+                .iter()
+                .map(|(configs, registry)| async move {
+                    let makers = registry.get_makers_with_supported_tokens().await?;
+
+                    Ok::<_, RegistryError>(versioned_makers(makers, configs).await)
+                }),
+        )
+        .await?;
+
+        Ok(makers.into_iter().flatten().collect())
+    }
+
+    /// The batch of each registry, labeled by the latest version sharing it, a failing registry
+    /// not failing the others.
+    pub async fn get_makers_with_supported_tokens_batch(
+        &self,
+        options: &BatchOptions,
+    ) -> Vec<(Config, Result<MakerBatch, RegistryError>)> {
+        join_all(
+            self.registries
+                .iter()
+                .map(|(configs, registry)| async move {
+                    let config = latest(configs);
+                    let batch = registry
+                        .get_makers_with_supported_tokens_batch(options)
+                        .await;
+
+                    (config.clone(), batch)
+                }),
+        )
+        .await
+    }
+
+    /// The makers merged by address across the registries.
+    pub async fn get_merged_makers(&self) -> Result<Vec<MergedMaker>, RegistryError> {
+        Ok(merge_makers(self.get_makers_with_supported_tokens().await?))
+    }
+}
+
+async fn versioned_makers<I>(makers: I, configs: &[Config]) -> Vec<VersionedMaker>
+where
+    I: IntoIterator<Item = MakerWithSupportedTokens>,
+{
+    let supported = stream::iter(makers)
+        .map(|maker| async move {
+            let configs = supported_configs(&maker, configs).await;
+            (maker, configs)
+        })
+        .buffered(PROTOCOLS_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    supported
+        .into_iter()
+        .flat_map(|(maker, configs)| {
+            configs
+                .into_iter()
+                .map(move |config| VersionedMaker::new(maker.clone(), config))
+        })
+        .collect()
+}
+
+// The configs of the versions the maker quotes for. A maker that can't list its protocols is
+// assumed to support every version of the registry.
+async fn supported_configs(maker: &MakerWithSupportedTokens, configs: &[Config]) -> Vec<Config> {
+    if configs.len() == 1 {
+        return configs.to_vec();
+    }
+
+    match MakerClient::from_config(maker.clone(), latest(configs).clone())
+        .get_protocols()
+        .await
+    {
+        Ok(protocols) => configs_for_protocols(&protocols.0, configs),
+        Err(err) => {
+            warn!("Can't get the protocols of {maker}: {err}");
+            configs.to_vec()
+        }
+    }
+}
+
+// The configs whose swap contract is listed in the protocols. A maker whose protocols match no
+// known swap contract is assumed to support every version rather than dropped.
+fn configs_for_protocols(protocols: &[Protocol], configs: &[Config]) -> Vec<Config> {
+    let supported: Vec<Config> = configs
+        .iter()
+        .filter(|config| {
+            protocols.iter().any(|p| {
+                p.params.chain_id.parse() == Ok(config.chain_id)
+                    && p.params.swap_contract_address.parse() == Ok(config.swap_address)
+            })
+        })
+        .cloned()
+        .collect();
+
+    if supported.is_empty() {
+        warn!("No known swap contract in the protocols {protocols:?}, assuming every version");
+        return configs.to_vec();
+    }
+
+    supported
+}
+
+// Groups the configs by registry, keeping the order of their first occurrence
+fn group_by_registry(configs: Vec<Config>) -> Vec<Vec<Config>> {
+    let mut groups: Vec<Vec<Config>> = vec![];
+
+    for config in configs {
+        match groups
+            .iter_mut()
+            .find(|g| g[0].registry_address == config.registry_address)
+        {
+            Some(group) => group.push(config),
+            None => groups.push(vec![config]),
+        }
+    }
+
+    groups
+}
+
+fn latest(configs: &[Config]) -> &Config {
+    configs.last().expect("A registry has at least one config")
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use crate::{
+        chain_configs,
+        json_rpc::{Protocol, ProtocolParams},
+        Config, ProtocolVersion,
+    };
+
+    use super::{configs_for_protocols, group_by_registry};
+
+    fn protocol(chain_id: u64, swap_address: Address) -> Protocol {
+        Protocol {
+            interface_id: String::new(),
+            params: ProtocolParams {
+                chain_id: chain_id.to_string(),
+                swap_contract_address: swap_address.to_string(),
+                wallet_address: Address::repeat_byte(1).to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn versions_from_protocols() {
+        let configs = [Config::mainnet_v4(), Config::mainnet_v5()];
+        let v5 = configs[1].swap_address;
+
+        let supported = configs_for_protocols(&[protocol(1, v5)], &configs);
+        assert_eq!(supported, vec![configs[1].clone()]);
+
+        // Another chain
+        assert_eq!(
+            configs_for_protocols(&[protocol(137, v5)], &configs),
+            configs
+        );
+    }
+
+    #[test]
+    fn unknown_swap_contracts() {
+        let configs = [Config::mainnet_v4(), Config::mainnet_v5()];
+
+        assert_eq!(
+            configs_for_protocols(&[protocol(1, Address::repeat_byte(2))], &configs),
+            configs
+        );
+        assert_eq!(configs_for_protocols(&[], &configs), configs);
+    }
+
+    #[test]
+    fn shared_registries() {
+        let groups = group_by_registry(chain_configs(1));
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|g| g.iter().map(|c| c.protocol_version).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![ProtocolVersion::Legacy],
+                vec![ProtocolVersion::V4, ProtocolVersion::V5]
+            ]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, ProtocolVersion};
+
+/// Identifies the registry a cache has been filled from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegistryCacheKey {
+    pub chain_id: u64,
+    pub protocol_version: ProtocolVersion,
+    pub registry_address: Address,
+}
+
+impl RegistryCacheKey {
+    pub fn file_name(&self) -> String {
+        format!(
+            "registry-{}-{}-{}.json",
+            self.chain_id, self.protocol_version, self.registry_address
+        )
+    }
+}
+
+impl From<&Config> for RegistryCacheKey {
+    fn from(config: &Config) -> Self {
+        Self {
+            chain_id: config.chain_id,
+            protocol_version: config.protocol_version,
+            registry_address: config.registry_address,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::{fs::File, io::Read, path::Path};
+
+use alloy::primitives::Address;
+use async_trait::async_trait;
+
+use crate::{Maker, MakerWithSupportedTokens};
+
+use super::{RegistryContract, RegistryError};
+
+/// An in-memory registry, e.g. loaded from a JSON list of
+/// `{ "address": ..., "url": ..., "supported_tokens": [...] }`.
+#[derive(Debug, Clone, Default)]
+pub struct StaticRegistry {
+    makers: Vec<MakerWithSupportedTokens>,
+}
+
+impl StaticRegistry {
+    pub fn new(makers: Vec<MakerWithSupportedTokens>) -> Self {
+        Self { makers }
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, RegistryError> {
+        Ok(Self::new(serde_json::from_reader(reader)?))
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, RegistryError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, RegistryError> {
+        Ok(Self::new(serde_json::from_str(s)?))
+    }
+
+    /// Adds the maker, replacing any maker with the same address.
+    pub fn insert(&mut self, maker: MakerWithSupportedTokens) {
+        self.makers.retain(|m| m.address() != maker.address());
+        self.makers.push(maker);
+    }
+
+    pub fn makers(&self) -> &[MakerWithSupportedTokens] {
+        &self.makers
+    }
+
+    fn find(&self, address: Address) -> Result<&MakerWithSupportedTokens, RegistryError> {
+        self.makers
+            .iter()
+            .find(|m| *m.address() == address)
+            .ok_or(RegistryError::MakerNotFound(address))
+    }
+}
+
+#[async_trait]
+impl RegistryContract for StaticRegistry {
+    async fn get_maker(&self, address: Address) -> Result<Maker, RegistryError> {
+        Ok(self.find(address)?.maker.clone())
+    }
+
+    async fn get_makers(&self) -> Result<Vec<Maker>, RegistryError> {
+        Ok(self.makers.iter().map(|m| m.maker.clone()).collect())
+    }
+
+    async fn get_tokens(&self, maker_address: Address) -> Result<Vec<Address>, RegistryError> {
+        Ok(self.find(maker_address)?.supported_tokens.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use crate::{registry::RegistryError, RegistryClient};
+
+    use super::StaticRegistry;
+
+    #[tokio::test]
+    async fn static_registry() {
+        let registry = StaticRegistry::from_json_str(
+            r#"[
+                {
+                    "address": "0x0101010101010101010101010101010101010101",
+                    "url": "https://maker.example",
+                    "supported_tokens": ["0x0202020202020202020202020202020202020202"]
+                },
+                {
+                    "address": "0x0303030303030303030303030303030303030303",
+                    "url": "https://other.example"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let client = RegistryClient::from_contract(registry);
+
+        let makers = client.get_makers_with_supported_tokens().await.unwrap();
+
+        assert_eq!(makers.len(), 2);
+        assert_eq!(makers[0].supported_tokens, vec![Address::repeat_byte(2)]);
+        assert!(makers[1].supported_tokens.is_empty());
+        assert!(matches!(
+            client.get_maker(Address::ZERO).await,
+            Err(RegistryError::MakerNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use crate::{Config, MakerClient, MakerWithSupportedTokens, ProtocolVersion};
+
+#[derive(Debug, Clone)]
+pub struct VersionedMaker {
+    pub maker: MakerWithSupportedTokens,
+    // The deployment of a version the maker quotes for, whose registry it is registered in
+    pub config: Config,
+}
+
+impl VersionedMaker {
+    pub fn new(maker: MakerWithSupportedTokens, config: Config) -> Self {
+        Self { maker, config }
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.config.protocol_version
+    }
+
+    /// A client quoting against the swap contract of the maker's version.
+    pub fn client(&self) -> MakerClient {
+        MakerClient::from_config(self.maker.clone(), self.config.clone())
+    }
+}
+
+impl Display for VersionedMaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.maker, self.config.protocol_version)
+    }
+}
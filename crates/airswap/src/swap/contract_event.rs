@@ -0,0 +1,204 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, BlockNumber, B256},
+    providers::Provider,
+    rpc::types::eth::{BlockNumberOrTag, Filter, Log},
+    sol_types::SolEventInterface,
+    transports::Transport,
+};
+use futures::stream::BoxStream;
+
+use super::{
+    history::get_history,
+    watch::watch_events,
+    EventRecord, StreamEvent,
+    SwapERC20Contract::{self, SwapERC20ContractEvents},
+    SwapError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwapContractEvent {
+    Swap(SwapERC20Contract::SwapERC20),
+    Cancel(SwapERC20Contract::Cancel),
+    Authorize(SwapERC20Contract::Authorize),
+    Revoke(SwapERC20Contract::Revoke),
+    SetProtocolFee(SwapERC20Contract::SetProtocolFee),
+    SetProtocolFeeLight(SwapERC20Contract::SetProtocolFeeLight),
+    SetProtocolFeeWallet(SwapERC20Contract::SetProtocolFeeWallet),
+    SetRebateMax(SwapERC20Contract::SetRebateMax),
+    SetRebateScale(SwapERC20Contract::SetRebateScale),
+    SetStaking(SwapERC20Contract::SetStaking),
+    OwnershipTransferStarted(SwapERC20Contract::OwnershipTransferStarted),
+    OwnershipTransferred(SwapERC20Contract::OwnershipTransferred),
+}
+
+impl SwapContractEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SwapContractEvent::Swap(_) => "SwapERC20",
+            SwapContractEvent::Cancel(_) => "Cancel",
+            SwapContractEvent::Authorize(_) => "Authorize",
+            SwapContractEvent::Revoke(_) => "Revoke",
+            SwapContractEvent::SetProtocolFee(_) => "SetProtocolFee",
+            SwapContractEvent::SetProtocolFeeLight(_) => "SetProtocolFeeLight",
+            SwapContractEvent::SetProtocolFeeWallet(_) => "SetProtocolFeeWallet",
+            SwapContractEvent::SetRebateMax(_) => "SetRebateMax",
+            SwapContractEvent::SetRebateScale(_) => "SetRebateScale",
+            SwapContractEvent::SetStaking(_) => "SetStaking",
+            SwapContractEvent::OwnershipTransferStarted(_) => "OwnershipTransferStarted",
+            SwapContractEvent::OwnershipTransferred(_) => "OwnershipTransferred",
+        }
+    }
+
+    pub fn is_swap(&self) -> bool {
+        matches!(self, SwapContractEvent::Swap(_))
+    }
+
+    pub fn is_fee_change(&self) -> bool {
+        matches!(
+            self,
+            SwapContractEvent::SetProtocolFee(_)
+                | SwapContractEvent::SetProtocolFeeLight(_)
+                | SwapContractEvent::SetProtocolFeeWallet(_)
+                | SwapContractEvent::SetRebateMax(_)
+                | SwapContractEvent::SetRebateScale(_)
+        )
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.is_fee_change()
+            || matches!(
+                self,
+                SwapContractEvent::SetStaking(_)
+                    | SwapContractEvent::OwnershipTransferStarted(_)
+                    | SwapContractEvent::OwnershipTransferred(_)
+            )
+    }
+
+    /// The signer wallet concerned by the event, if any.
+    pub fn signer_wallet(&self) -> Option<Address> {
+        match self {
+            SwapContractEvent::Swap(e) => Some(e.signerWallet),
+            SwapContractEvent::Cancel(e) => Some(e.signerWallet),
+            SwapContractEvent::Authorize(e) => Some(e.signerWallet),
+            SwapContractEvent::Revoke(e) => Some(e.signerWallet),
+            _ => None,
+        }
+    }
+}
+
+impl From<SwapERC20ContractEvents> for SwapContractEvent {
+    fn from(value: SwapERC20ContractEvents) -> Self {
+        match value {
+            SwapERC20ContractEvents::SwapERC20(e) => SwapContractEvent::Swap(e),
+            SwapERC20ContractEvents::Cancel(e) => SwapContractEvent::Cancel(e),
+            SwapERC20ContractEvents::Authorize(e) => SwapContractEvent::Authorize(e),
+            SwapERC20ContractEvents::Revoke(e) => SwapContractEvent::Revoke(e),
+            SwapERC20ContractEvents::SetProtocolFee(e) => SwapContractEvent::SetProtocolFee(e),
+            SwapERC20ContractEvents::SetProtocolFeeLight(e) => {
+                SwapContractEvent::SetProtocolFeeLight(e)
+            }
+            SwapERC20ContractEvents::SetProtocolFeeWallet(e) => {
+                SwapContractEvent::SetProtocolFeeWallet(e)
+            }
+            SwapERC20ContractEvents::SetRebateMax(e) => SwapContractEvent::SetRebateMax(e),
+            SwapERC20ContractEvents::SetRebateScale(e) => SwapContractEvent::SetRebateScale(e),
+            SwapERC20ContractEvents::SetStaking(e) => SwapContractEvent::SetStaking(e),
+            SwapERC20ContractEvents::OwnershipTransferStarted(e) => {
+                SwapContractEvent::OwnershipTransferStarted(e)
+            }
+            SwapERC20ContractEvents::OwnershipTransferred(e) => {
+                SwapContractEvent::OwnershipTransferred(e)
+            }
+        }
+    }
+}
+
+impl TryFrom<&Log> for SwapContractEvent {
+    type Error = SwapError;
+
+    fn try_from(log: &Log) -> Result<Self, Self::Error> {
+        let event = SwapERC20ContractEvents::decode_raw_log(log.topics(), &log.data().data, true)?;
+
+        Ok(event.into())
+    }
+}
+
+fn swap_contract_filter(swap_address: Address) -> Filter {
+    let signatures = SwapERC20ContractEvents::SELECTORS
+        .iter()
+        .copied()
+        .map(B256::from)
+        .collect::<Vec<_>>();
+
+    Filter::new()
+        .address(swap_address)
+        .event_signature(signatures)
+}
+
+pub async fn get_swap_contract_history<P, B, T, N>(
+    provider: &P,
+    swap_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Result<Vec<EventRecord<SwapContractEvent>>, SwapError>
+where
+    P: Provider<T, N>,
+    B: Into<BlockNumberOrTag>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let filter = swap_contract_filter(swap_address)
+        .from_block(from_block)
+        .to_block(to_block.map(|b| b.into()).unwrap_or_default());
+
+    get_history(provider, &filter, |log| SwapContractEvent::try_from(log)).await
+}
+
+pub fn watch_swap_contract_events<P, T, N>(
+    provider: P,
+    swap_address: Address,
+    from_block: Option<BlockNumber>,
+) -> BoxStream<'static, Result<StreamEvent<SwapContractEvent>, SwapError>>
+where
+    P: Provider<T, N> + Clone + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    watch_events(
+        provider,
+        swap_contract_filter(swap_address),
+        from_block,
+        |log| SwapContractEvent::try_from(log),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{self, Address, U256},
+        rpc::types::eth::Log,
+        sol_types::SolEvent,
+    };
+
+    use super::{SwapContractEvent, SwapERC20Contract};
+
+    #[test]
+    fn decode_fee_change() {
+        let event = SwapERC20Contract::SetProtocolFee {
+            protocolFee: U256::from(7),
+        };
+        let log = Log {
+            inner: primitives::Log {
+                address: Address::ZERO,
+                data: event.encode_log_data(),
+            },
+            ..Default::default()
+        };
+
+        let decoded = SwapContractEvent::try_from(&log).unwrap();
+
+        assert!(decoded.is_fee_change());
+        assert_eq!(decoded, SwapContractEvent::SetProtocolFee(event));
+    }
+}
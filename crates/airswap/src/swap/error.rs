@@ -0,0 +1,24 @@
+use alloy::{rpc::json_rpc::ErrorPayload, transports::TransportError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SwapError {
+    #[error("{0}")]
+    Payload(ErrorPayload),
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error(transparent)]
+    Sol(#[from] alloy::sol_types::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("Malformed response")]
+    MalformedResponse,
+    #[error("Receive error")]
+    Receive,
+    #[error("The log is missing its {0}")]
+    IncompleteLog(&'static str),
+    #[error("The block {0} can't be found")]
+    BlockNotFound(u64),
+    #[error("The provider can't subscribe to logs")]
+    SubscriptionUnsupported,
+}
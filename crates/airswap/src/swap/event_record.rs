@@ -0,0 +1,65 @@
+use alloy::{
+    primitives::{Address, BlockHash, BlockNumber, TxHash},
+    rpc::types::eth::Log,
+};
+
+use super::{SwapERC20Contract, SwapError};
+
+pub type SwapRecord = EventRecord<SwapERC20Contract::SwapERC20>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRecord<E> {
+    pub event: E,
+    pub address: Address,
+    pub block_number: BlockNumber,
+    pub block_hash: BlockHash,
+    pub timestamp: Option<u64>,
+    pub transaction_hash: TxHash,
+    pub transaction_index: u64,
+    pub log_index: u64,
+}
+
+impl<E> EventRecord<E> {
+    pub fn new(log: &Log, event: E) -> Result<Self, SwapError> {
+        Ok(Self {
+            event,
+            address: log.address(),
+            block_number: log
+                .block_number
+                .ok_or(SwapError::IncompleteLog("block number"))?,
+            block_hash: log
+                .block_hash
+                .ok_or(SwapError::IncompleteLog("block hash"))?,
+            timestamp: log.block_timestamp,
+            transaction_hash: log
+                .transaction_hash
+                .ok_or(SwapError::IncompleteLog("transaction hash"))?,
+            transaction_index: log
+                .transaction_index
+                .ok_or(SwapError::IncompleteLog("transaction index"))?,
+            log_index: log.log_index.ok_or(SwapError::IncompleteLog("log index"))?,
+        })
+    }
+
+    /// Identifies the log independently of the block it has been included in,
+    /// so the same swap seen before and after a reorg can be deduplicated.
+    pub fn key(&self) -> (TxHash, u64) {
+        (self.transaction_hash, self.log_index)
+    }
+
+    pub fn map<F, U>(self, f: F) -> EventRecord<U>
+    where
+        F: FnOnce(E) -> U,
+    {
+        EventRecord {
+            event: f(self.event),
+            address: self.address,
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+            timestamp: self.timestamp,
+            transaction_hash: self.transaction_hash,
+            transaction_index: self.transaction_index,
+            log_index: self.log_index,
+        }
+    }
+}
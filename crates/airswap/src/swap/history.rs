@@ -0,0 +1,178 @@
+use std::collections::{BTreeSet, HashMap};
+
+use alloy::{
+    network::{BlockResponse, HeaderResponse, Network},
+    primitives::{Address, BlockNumber},
+    providers::Provider,
+    rpc::types::eth::{BlockNumberOrTag, Filter, Log},
+    sol_types::SolEvent,
+    transports::Transport,
+};
+use futures::{stream, StreamExt};
+use tracing::warn;
+
+use super::{EventRecord, SwapERC20Contract, SwapError, SwapRecord};
+
+const TIMESTAMP_CONCURRENCY: usize = 8;
+
+pub async fn get_swap_events<P, B, T, N>(
+    provider: &P,
+    swap_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Result<Vec<SwapERC20Contract::SwapERC20>, SwapError>
+where
+    P: Provider<T, N>,
+    B: Into<BlockNumberOrTag>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let filter = swap_filter(swap_address, from_block, to_block);
+    let logs = provider.get_logs(&filter).await?;
+    let mut events = vec![];
+
+    for log in logs.into_iter().filter(|l| !l.removed) {
+        events.push(SwapERC20Contract::SwapERC20::decode_log_data(
+            log.data(),
+            true,
+        )?);
+    }
+
+    Ok(events)
+}
+
+/// The swap records, their timestamp being set only when the node includes it in the logs,
+/// see [`get_swap_history_with_timestamps`].
+pub async fn get_swap_history<P, B, T, N>(
+    provider: &P,
+    swap_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Result<Vec<SwapRecord>, SwapError>
+where
+    P: Provider<T, N>,
+    B: Into<BlockNumberOrTag>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let filter = swap_filter(swap_address, from_block, to_block);
+
+    get_history(provider, &filter, |log| {
+        SwapERC20Contract::SwapERC20::decode_log_data(log.data(), true).map_err(Into::into)
+    })
+    .await
+}
+
+/// The swap records, with the timestamps missing from the logs fetched from their blocks.
+/// Records whose block can't be fetched are kept without timestamp.
+pub async fn get_swap_history_with_timestamps<P, B, T, N>(
+    provider: &P,
+    swap_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Result<Vec<SwapRecord>, SwapError>
+where
+    P: Provider<T, N>,
+    B: Into<BlockNumberOrTag>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let mut records = get_swap_history(provider, swap_address, from_block, to_block).await?;
+
+    for (number, err) in fill_timestamps(provider, &mut records, TIMESTAMP_CONCURRENCY).await {
+        warn!("Can't fetch the timestamp of block {number}: {err}");
+    }
+
+    Ok(records)
+}
+
+fn swap_filter<B: Into<BlockNumberOrTag>>(
+    swap_address: Address,
+    from_block: B,
+    to_block: Option<B>,
+) -> Filter {
+    Filter::new()
+        .from_block(from_block)
+        .to_block(to_block.map(|b| b.into()).unwrap_or_default())
+        .address(swap_address)
+        .event(SwapERC20Contract::SwapERC20::SIGNATURE)
+}
+
+pub(crate) async fn get_history<P, T, N, E>(
+    provider: &P,
+    filter: &Filter,
+    decode: fn(&Log) -> Result<E, SwapError>,
+) -> Result<Vec<EventRecord<E>>, SwapError>
+where
+    P: Provider<T, N>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let logs = provider.get_logs(filter).await?;
+    let mut records = vec![];
+
+    for log in logs.into_iter().filter(|l| !l.removed) {
+        let event = decode(&log)?;
+
+        records.push(EventRecord::new(&log, event)?);
+    }
+
+    Ok(records)
+}
+
+/// Sets the missing timestamps from the blocks of the records, fetching at most
+/// `concurrency` blocks at once. Returns the blocks that couldn't be fetched.
+pub async fn fill_timestamps<P, T, N, E>(
+    provider: &P,
+    records: &mut [EventRecord<E>],
+    concurrency: usize,
+) -> Vec<(BlockNumber, SwapError)>
+where
+    P: Provider<T, N>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let missing = records
+        .iter()
+        .filter(|r| r.timestamp.is_none())
+        .map(|r| r.block_number)
+        .collect::<BTreeSet<_>>();
+
+    let results = stream::iter(missing)
+        .map(|number| async move { (number, get_block_timestamp(provider, number).await) })
+        .buffered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut timestamps = HashMap::new();
+    let mut failures = vec![];
+
+    for (number, result) in results {
+        match result {
+            Ok(timestamp) => {
+                timestamps.insert(number, timestamp);
+            }
+            Err(err) => failures.push((number, err)),
+        }
+    }
+
+    for record in records.iter_mut().filter(|r| r.timestamp.is_none()) {
+        record.timestamp = timestamps.get(&record.block_number).copied();
+    }
+
+    failures
+}
+
+async fn get_block_timestamp<P, T, N>(provider: &P, number: BlockNumber) -> Result<u64, SwapError>
+where
+    P: Provider<T, N>,
+    N: Network,
+    T: Transport + Clone,
+{
+    let block = provider
+        .get_block_by_number(number.into(), false)
+        .await?
+        .ok_or(SwapError::BlockNotFound(number))?;
+
+    Ok(block.header().timestamp())
+}
@@ -0,0 +1,72 @@
+use alloy::primitives::Address;
+use alloy::{
+    pubsub::PubSubFrontend,
+    rpc::{
+        json_rpc::{Id, Request, RequestMeta, ResponsePayload},
+        types::eth::{
+            pubsub::{Params, SubscriptionKind},
+            Filter, Log,
+        },
+    },
+    sol_types::SolEvent,
+};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use tracing::error;
+
+use super::{SwapERC20Contract, SwapError};
+
+pub async fn get_swap_events_stream(
+    front_end: &PubSubFrontend,
+    swap_address: Address,
+    id: Id,
+) -> Result<BoxStream<'_, Result<SwapERC20Contract::SwapERC20, SwapError>>, SwapError> {
+    let stringified_id = id.to_string();
+    let req = Request {
+        meta: RequestMeta::new("eth_subscribe".into(), id),
+        params: [
+            serde_json::to_value(SubscriptionKind::Logs)?,
+            serde_json::to_value(Params::Logs(Box::new(
+                Filter::new()
+                    .address(swap_address)
+                    .event_signature(SwapERC20Contract::SwapERC20::SIGNATURE_HASH),
+            )))?,
+        ],
+    };
+
+    let response = front_end
+        .send(req.serialize()?)
+        .await?
+        .deser_success()
+        .map_err(|_| SwapError::MalformedResponse)?;
+
+    let subscription_id = match response.payload {
+        ResponsePayload::Success(subscription_id) => Ok(subscription_id),
+        ResponsePayload::Failure(err) => Err(SwapError::Payload(err)),
+    }?;
+
+    let rx = front_end.get_subscription(subscription_id).await?;
+
+    let stream = stream::unfold(
+        (rx, stringified_id),
+        |(mut rx, stringified_id)| async move {
+            match rx.recv().await {
+                Ok(value) => Some((value, (rx, stringified_id))),
+                Err(err) => {
+                    error!("Subscription {stringified_id} ended: {err}");
+                    None
+                }
+            }
+        },
+    );
+
+    let stream = stream
+        .map(|value| serde_json::from_str::<Log>(value.get()).map_err(Into::into))
+        .and_then(|log| async move {
+            SwapERC20Contract::SwapERC20::decode_log_data(log.data(), true).map_err(Into::into)
+        });
+
+    Ok(stream.boxed())
+}
@@ -0,0 +1,442 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use alloy::{
+    network::Network,
+    primitives::{Address, BlockHash, BlockNumber, TxHash},
+    providers::Provider,
+    pubsub::Subscription,
+    rpc::types::eth::{Filter, Log},
+    sol_types::SolEvent,
+    transports::{RpcError, Transport, TransportErrorKind},
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::{sync::broadcast::error::RecvError, time::sleep};
+use tracing::{error, warn};
+
+use super::{EventRecord, SwapERC20Contract, SwapError};
+
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent<E> {
+    Added(EventRecord<E>),
+    // The log has been removed from the canonical chain by a reorg
+    Removed(EventRecord<E>),
+}
+
+impl<E> StreamEvent<E> {
+    pub fn record(&self) -> &EventRecord<E> {
+        match self {
+            StreamEvent::Added(record) | StreamEvent::Removed(record) => record,
+        }
+    }
+
+    pub fn is_removed(&self) -> bool {
+        matches!(self, StreamEvent::Removed(_))
+    }
+}
+
+pub fn watch_swap_events<P, T, N>(
+    provider: P,
+    swap_address: Address,
+    from_block: Option<BlockNumber>,
+) -> BoxStream<'static, Result<StreamEvent<SwapERC20Contract::SwapERC20>, SwapError>>
+where
+    P: Provider<T, N> + Clone + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    let filter = Filter::new()
+        .address(swap_address)
+        .event_signature(SwapERC20Contract::SwapERC20::SIGNATURE_HASH);
+
+    watch_events(provider, filter, from_block, |log| {
+        SwapERC20Contract::SwapERC20::decode_log_data(log.data(), true).map_err(Into::into)
+    })
+}
+
+pub(crate) fn watch_events<P, T, N, E>(
+    provider: P,
+    filter: Filter,
+    from_block: Option<BlockNumber>,
+    decode: fn(&Log) -> Result<E, SwapError>,
+) -> BoxStream<'static, Result<StreamEvent<E>, SwapError>>
+where
+    P: Provider<T, N> + Clone + 'static,
+    T: Transport + Clone,
+    N: Network,
+    E: Send + 'static,
+{
+    let state = WatchState {
+        provider,
+        filter,
+        tracker: LogTracker::new(decode, from_block),
+        subscription: None,
+        pending: VecDeque::new(),
+        backoff: MIN_BACKOFF,
+        done: false,
+        phantom: Default::default(),
+    };
+
+    stream::unfold(state, |mut state| async move {
+        let item = state.next().await?;
+        Some((item, state))
+    })
+    .boxed()
+}
+
+// Identifies a log in a given block
+type LogKey = (BlockHash, TxHash, u64);
+
+// How many blocks of emitted logs are kept to dedupe the backfills
+const DEDUPE_DEPTH: u64 = 256;
+
+/// Tracks the emitted logs, to know where to backfill from and which logs to skip.
+struct LogTracker<E> {
+    decode: fn(&Log) -> Result<E, SwapError>,
+    // The block the next backfill starts from, included
+    from_block: Option<BlockNumber>,
+    // The emitted logs, kept to emit their removal
+    seen: BTreeMap<BlockNumber, HashMap<LogKey, Log>>,
+}
+
+impl<E> LogTracker<E> {
+    fn new(decode: fn(&Log) -> Result<E, SwapError>, from_block: Option<BlockNumber>) -> Self {
+        Self {
+            decode,
+            from_block,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    // Starts the backfills at the head seen on the first connection, when no start was given
+    fn on_head(&mut self, head: BlockNumber) {
+        self.from_block.get_or_insert(head);
+    }
+
+    // The emitted blocks are fetched again, to find the logs removed while disconnected
+    fn backfill_from(&self) -> Option<BlockNumber> {
+        let from_block = self.from_block?;
+
+        Some(
+            self.seen
+                .keys()
+                .next()
+                .map_or(from_block, |&b| b.min(from_block)),
+        )
+    }
+
+    // Handles the logs of a backfill starting at `from_block`: the emitted logs of these blocks
+    // missing from it, e.g. because their block hash changed, have been removed by a reorg
+    fn handle_backfill(
+        &mut self,
+        from_block: BlockNumber,
+        logs: &[Log],
+    ) -> Vec<Result<StreamEvent<E>, SwapError>> {
+        let canonical = logs.iter().filter_map(log_key).collect::<HashSet<_>>();
+        let mut removed = self
+            .seen
+            .range(from_block..)
+            .flat_map(|(_, logs)| logs.iter())
+            .filter(|(key, _)| !canonical.contains(key))
+            .map(|(_, log)| Log {
+                removed: true,
+                ..log.clone()
+            })
+            .collect::<Vec<_>>();
+        removed.sort_by_key(|log| (log.block_number, log.log_index));
+
+        removed
+            .iter()
+            .chain(logs)
+            .filter_map(|log| self.handle_log(log))
+            .collect()
+    }
+
+    fn handle_log(&mut self, log: &Log) -> Option<Result<StreamEvent<E>, SwapError>> {
+        let record = match (self.decode)(log).and_then(|event| EventRecord::new(log, event)) {
+            Ok(record) => record,
+            Err(err) => return Some(Err(err)),
+        };
+        let key = (record.block_hash, record.transaction_hash, record.log_index);
+
+        if log.removed {
+            if let Some(keys) = self.seen.get_mut(&record.block_number) {
+                keys.remove(&key);
+            }
+
+            // The replacement logs of the block may have any index, backfill the whole block
+            self.from_block = Some(
+                self.from_block
+                    .map_or(record.block_number, |b| b.min(record.block_number)),
+            );
+
+            return Some(Ok(StreamEvent::Removed(record)));
+        }
+
+        if self
+            .seen
+            .entry(record.block_number)
+            .or_default()
+            .insert(key, log.clone())
+            .is_some()
+        {
+            // Already emitted, either by a backfill or by the subscription
+            return None;
+        }
+
+        self.from_block = Some(
+            self.from_block
+                .map_or(record.block_number, |b| b.max(record.block_number)),
+        );
+        self.prune();
+
+        Some(Ok(StreamEvent::Added(record)))
+    }
+
+    fn prune(&mut self) {
+        if let Some(&last) = self.seen.keys().next_back() {
+            self.seen = self.seen.split_off(&last.saturating_sub(DEDUPE_DEPTH));
+        }
+    }
+}
+
+fn log_key(log: &Log) -> Option<LogKey> {
+    Some((log.block_hash?, log.transaction_hash?, log.log_index?))
+}
+
+struct WatchState<P, T, N, E> {
+    provider: P,
+    filter: Filter,
+    tracker: LogTracker<E>,
+    subscription: Option<Subscription<Log>>,
+    pending: VecDeque<Result<StreamEvent<E>, SwapError>>,
+    backoff: Duration,
+    // The provider can't subscribe, the stream ends
+    done: bool,
+    phantom: std::marker::PhantomData<(T, N)>,
+}
+
+impl<P, T, N, E> WatchState<P, T, N, E>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn next(&mut self) -> Option<Result<StreamEvent<E>, SwapError>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let Some(subscription) = self.subscription.as_mut() else {
+                if let Err(err) = self.connect().await {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                continue;
+            };
+
+            match subscription.recv_result().await {
+                Ok(Ok(log)) => {
+                    if let Some(event) = self.tracker.handle_log(&log) {
+                        return Some(event);
+                    }
+                }
+                Ok(Err(err)) => return Some(Err(err.into())),
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Swap events subscription lagged by {skipped} logs, backfilling");
+                    self.subscription = None;
+                }
+                Err(RecvError::Closed) => {
+                    warn!("Swap events subscription closed, resubscribing");
+                    self.subscription = None;
+                }
+            }
+        }
+    }
+
+    // Subscribes then backfills the logs emitted since the last seen block, retrying with an
+    // exponential backoff until both succeed, unless the provider can't subscribe at all
+    async fn connect(&mut self) -> Result<(), SwapError> {
+        loop {
+            match self.try_connect().await {
+                Ok(()) => {
+                    self.backoff = MIN_BACKOFF;
+                    return Ok(());
+                }
+                Err(SwapError::Transport(RpcError::Transport(
+                    TransportErrorKind::PubsubUnavailable,
+                ))) => return Err(SwapError::SubscriptionUnsupported),
+                Err(err) => {
+                    error!("Can't subscribe to swap events: {err}");
+                    sleep(self.backoff).await;
+                    self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    async fn try_connect(&mut self) -> Result<(), SwapError> {
+        let subscription = self.provider.subscribe_logs(&self.filter).await?;
+
+        match self.tracker.backfill_from() {
+            Some(from_block) => {
+                let filter = self.filter.clone().from_block(from_block);
+                let logs = self.provider.get_logs(&filter).await?;
+
+                self.pending
+                    .extend(self.tracker.handle_backfill(from_block, &logs));
+            }
+            None => self
+                .tracker
+                .on_head(self.provider.get_block_number().await?),
+        }
+
+        self.subscription = Some(subscription);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{Address, LogData, B256},
+        rpc::types::eth::Log,
+    };
+
+    use crate::swap::SwapError;
+
+    use super::{LogTracker, StreamEvent};
+
+    fn log(block: u64, block_hash: u8, log_index: u64, removed: bool) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::ZERO,
+                data: LogData::default(),
+            },
+            block_hash: Some(B256::repeat_byte(block_hash)),
+            block_number: Some(block),
+            block_timestamp: None,
+            transaction_hash: Some(B256::repeat_byte(log_index as u8)),
+            transaction_index: Some(0),
+            log_index: Some(log_index),
+            removed,
+        }
+    }
+
+    fn tracker(from_block: Option<u64>) -> LogTracker<()> {
+        fn decode(_: &Log) -> Result<(), SwapError> {
+            Ok(())
+        }
+
+        LogTracker::new(decode, from_block)
+    }
+
+    fn added(tracker: &mut LogTracker<()>, log: &Log) -> bool {
+        matches!(tracker.handle_log(log), Some(Ok(StreamEvent::Added(_))))
+    }
+
+    #[test]
+    fn reorg_replacement_with_lower_index() {
+        let mut tracker = tracker(None);
+
+        assert!(added(&mut tracker, &log(100, 1, 5, false)));
+        assert!(matches!(
+            tracker.handle_log(&log(100, 1, 5, true)),
+            Some(Ok(StreamEvent::Removed(_)))
+        ));
+        assert_eq!(tracker.from_block, Some(100));
+
+        // The replacement block has the log at a lower index
+        assert!(added(&mut tracker, &log(100, 2, 2, false)));
+    }
+
+    #[test]
+    fn reorg_back_to_the_original_block() {
+        let mut tracker = tracker(None);
+
+        assert!(added(&mut tracker, &log(100, 1, 5, false)));
+        tracker.handle_log(&log(100, 1, 5, true));
+        assert!(added(&mut tracker, &log(100, 1, 5, false)));
+    }
+
+    #[test]
+    fn backfill_from_head_after_reconnect() {
+        let mut tracker = tracker(None);
+
+        // Nothing emitted yet, the first connection records the head
+        tracker.on_head(90);
+        assert_eq!(tracker.from_block, Some(90));
+
+        // A reconnection doesn't move the start of the backfill back to the new head
+        tracker.on_head(95);
+        assert_eq!(tracker.from_block, Some(90));
+
+        assert!(added(&mut tracker, &log(92, 1, 0, false)));
+        assert_eq!(tracker.from_block, Some(92));
+    }
+
+    #[test]
+    fn dedupe_backfill_and_subscription() {
+        let mut tracker = tracker(Some(100));
+
+        // Received through the subscription before the connection dropped
+        assert!(added(&mut tracker, &log(100, 1, 0, false)));
+        assert!(added(&mut tracker, &log(101, 2, 3, false)));
+        assert_eq!(tracker.from_block, Some(101));
+
+        // The backfill from block 101 returns the already emitted log, then the missed ones
+        let backfill = [
+            log(101, 2, 3, false),
+            log(101, 2, 4, false),
+            log(102, 3, 0, false),
+        ];
+        let emitted = backfill
+            .iter()
+            .filter(|log| added(&mut tracker, log))
+            .count();
+        assert_eq!(emitted, 2);
+
+        // The new subscription repeats the last backfilled log
+        assert!(tracker.handle_log(&log(102, 3, 0, false)).is_none());
+    }
+
+    #[test]
+    fn reorg_while_disconnected() {
+        let mut tracker = tracker(Some(100));
+
+        assert!(added(&mut tracker, &log(100, 1, 0, false)));
+        assert!(added(&mut tracker, &log(101, 2, 1, false)));
+        assert!(added(&mut tracker, &log(102, 3, 2, false)));
+
+        // While disconnected, the block 101 is replaced and the log of 102 is dropped
+        let from_block = tracker.backfill_from().unwrap();
+        assert_eq!(from_block, 100);
+
+        let events = tracker
+            .handle_backfill(from_block, &[log(100, 1, 0, false), log(101, 4, 1, false)])
+            .into_iter()
+            .map(|event| {
+                let event = event.unwrap();
+                (event.is_removed(), event.record().block_hash[0])
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(events, vec![(true, 2), (true, 3), (false, 4)]);
+
+        // The same backfill again changes nothing
+        assert!(tracker
+            .handle_backfill(100, &[log(100, 1, 0, false), log(101, 4, 1, false)])
+            .is_empty());
+    }
+}
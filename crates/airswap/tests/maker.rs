@@ -1,4 +1,4 @@
-use airswap::{Config, Maker, MakerClient, MakerWithSupportedTokens, ProtocolVersion};
+use airswap::{Config, Maker, MakerClient, MakerWithSupportedTokens};
 use alloy::primitives::{address, U256};
 
 #[tokio::test]
@@ -26,7 +26,7 @@ async fn test_maker() {
         ],
     };
 
-    let maker_client = MakerClient::new(1, maker, Config::new(1, ProtocolVersion::V4));
+    let maker_client = MakerClient::new(1, maker, Config::mainnet_v4());
     let amount = U256::from(2000000000);
 
     let payload = maker_client
@@ -0,0 +1,76 @@
+#![cfg(feature = "claim")]
+
+use std::env;
+
+use airswap::{
+    claim::{ClaimBook, Proposals},
+    pool::{get_pool_history, PoolAdmin, PoolClient, PoolContractErrors, PoolLedger, Unauthorized},
+    Config,
+};
+use alloy::{
+    primitives::{address, Address},
+    providers::ProviderBuilder,
+};
+use dotenv::dotenv;
+
+#[tokio::test]
+async fn test_claimable() {
+    dotenv().ok();
+
+    let eth_rpc = env::var("ETH_RPC_URL").unwrap();
+    let provider = ProviderBuilder::new().on_http(eth_rpc.parse().unwrap());
+    let pool_client =
+        PoolClient::from_config(provider, &Config::mainnet_v5(), ClaimBook::embedded()).unwrap();
+    let account = address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff");
+
+    let claims = pool_client.claim_book().get_claims(account).unwrap();
+    let claimable = pool_client.get_claimable(account).await.unwrap();
+
+    assert!(claimable.len() <= claims.len());
+    assert!(claimable.iter().all(|c| claims.contains(c)));
+}
+
+#[tokio::test]
+async fn test_tree_statuses() {
+    dotenv().ok();
+
+    let eth_rpc = env::var("ETH_RPC_URL").unwrap();
+    let provider = ProviderBuilder::new().on_http(eth_rpc.parse().unwrap());
+    let pool_admin =
+        PoolAdmin::from_config(provider, &Config::mainnet_v5(), Address::ZERO).unwrap();
+    let proposals = Proposals::embedded();
+
+    let statuses = pool_admin.get_tree_statuses(&proposals).await.unwrap();
+
+    assert_eq!(statuses.len(), proposals.len());
+    assert!(statuses.iter().all(|s| !s.is_conflicting()));
+
+    // Only admins can enable trees
+    if let Some(status) = statuses.iter().find(|s| s.is_pending()) {
+        let err = pool_admin
+            .dry_run_enable(status.tree, status.root)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.revert(),
+            Some(&PoolContractErrors::Unauthorized(Unauthorized {}))
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_pool_ledger() {
+    dotenv().ok();
+
+    let eth_rpc = env::var("ETH_RPC_URL").unwrap();
+    let provider = ProviderBuilder::new().on_http(eth_rpc.parse().unwrap());
+    let pool_address = Config::mainnet_v5().pool_address.unwrap();
+    let records = get_pool_history(&provider, pool_address, 20500000, Some(20600000))
+        .await
+        .unwrap();
+
+    let ledger = PoolLedger::from_records(ClaimBook::embedded(), records);
+
+    assert!(ledger.claims().all(|c| c.block_number >= 20500000));
+}
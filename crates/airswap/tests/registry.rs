@@ -1,6 +1,6 @@
 use std::{env, sync::Arc};
 
-use airswap::{Config, ProtocolVersion, RegistryClient};
+use airswap::{detect_deployments, Config, MultiRegistryClient, ProtocolVersion, RegistryClient};
 use alloy::primitives::{address, Address};
 use alloy::providers::ProviderBuilder;
 use dotenv::dotenv;
@@ -13,10 +13,29 @@ async fn test_registry() {
 
     let eth_rpc = env::var("ETH_RPC_URL").unwrap();
     let provider = ProviderBuilder::new().on_http(eth_rpc.parse().unwrap());
-    let config = Config::new(1, ProtocolVersion::Legacy);
+    let config = Config::mainnet_legacy();
     let registry_client = RegistryClient::new(Arc::new(provider), config);
 
     let maker = registry_client.get_maker(MYETH).await.unwrap();
 
     assert_eq!(maker.address, MYETH);
 }
+
+#[tokio::test]
+async fn test_detect_versions() {
+    dotenv().ok();
+
+    let eth_rpc = env::var("ETH_RPC_URL").unwrap();
+    let provider = Arc::new(ProviderBuilder::new().on_http(eth_rpc.parse().unwrap()));
+
+    let deployments = detect_deployments(&provider).await.unwrap();
+
+    assert!(deployments
+        .iter()
+        .any(|d| d.protocol_version() == ProtocolVersion::V4));
+
+    let registry = MultiRegistryClient::detect(provider).await.unwrap();
+    let makers = registry.get_makers().await.unwrap();
+
+    assert!(makers.iter().any(|(_, m)| m.address == MYETH));
+}
@@ -1,16 +1,14 @@
-use std::{collections::HashMap, env, fs::File};
+#![cfg(feature = "claim")]
+
+use std::env;
 
 use airswap::{
-    claim::{GroupedProposal, MerkleTree, ProposalGroup},
+    claim::{ClaimBook, ProposalGroup},
     pool::{rootsByTreeReturn, PoolContractInstance},
 };
 use alloy::providers::ProviderBuilder;
-use alloy::{
-    hex,
-    primitives::{address, B256},
-};
+use alloy::{hex, primitives::address};
 use dotenv::dotenv;
-use itertools::Itertools;
 
 #[tokio::test]
 async fn test_votes_for_proposals() {
@@ -36,21 +34,16 @@ async fn test_votes_for_proposals() {
 
     println!("root: {}", root);
 
-    let proposals = serde_json::from_reader::<_, HashMap<B256, GroupedProposal>>(
-        File::open("./proposals/proposals.json").unwrap(),
-    )
-    .unwrap();
+    let claim_book = ClaimBook::embedded();
 
     // Monthly Update: 1 Jan 2024
-    let proposal = proposals.get(&tree).unwrap();
-
-    let tree = MerkleTree::from_leaves(proposal.votes.iter().map(B256::from).sorted());
+    let proposal = claim_book.get_proposal(&tree).unwrap();
 
     for vote in &proposal.votes {
-        let proof = tree.get_proof(&B256::from(vote)).unwrap();
+        let claim = claim_book.get_claim(vote.address, &tree).unwrap().unwrap();
 
         let result = pool_instance
-            .verify(vote.address, root, vote.points, proof)
+            .verify(vote.address, root, claim.value, claim.proof)
             .call()
             .await
             .unwrap();
@@ -0,0 +1,98 @@
+#![cfg(feature = "claim")]
+
+use airswap::claim::{snapshot::SnapshotClient, MerkleTree, ProposalGroup};
+use alloy::primitives::{address, B256, U256};
+use serde_json::json;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+const PROPOSAL_1: &str = "0x6509ffd1d00d4862e94bd250d7dd0abbb77054c5ab28c289f614362bee805866";
+const PROPOSAL_2: &str = "0x77de42127551bd8007cf8493b1e584f0775f195a20f37583c5d267da5369aa49";
+
+// Serves canned Snapshot responses, picking them from the query found in the request body
+async fn serve_snapshot_stand_in(listener: TcpListener) {
+    loop {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![];
+        let mut buffer = [0; 4096];
+
+        let body = loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_string();
+
+            if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                let content_length = headers
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or_default();
+
+                if body.len() >= content_length {
+                    break body.to_string();
+                }
+            }
+        };
+
+        let response = if body.contains("proposals") {
+            json!({ "data": { "proposals": [
+                { "id": PROPOSAL_2, "title": "Monthly Update", "start": 1704067200, "end": 1704326400, "snapshot": "18900000", "state": "closed" },
+                { "id": PROPOSAL_1, "title": "Monthly Update", "start": 1704067200, "end": 1704326400, "snapshot": "18900000", "state": "closed" },
+            ]}})
+        } else {
+            json!({ "data": { "votes": [
+                { "voter": "0xE5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff", "vp": 12000.5, "proposal": { "id": PROPOSAL_1 } },
+                { "voter": "0xF9FDAd75c01EEd7619D975063Ed232C87577a7fd", "vp": 500.0, "proposal": { "id": PROPOSAL_1 } },
+                { "voter": "0xE5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff", "vp": 12001.5, "proposal": { "id": PROPOSAL_2 } },
+            ]}})
+        }
+        .to_string();
+
+        socket
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_grouped_proposals() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}/graphql", listener.local_addr().unwrap());
+
+    tokio::spawn(serve_snapshot_stand_in(listener));
+
+    let client = SnapshotClient::new(endpoint);
+    let proposals = client.get_grouped_proposals().await.unwrap();
+
+    let tree = ProposalGroup::new(vec![
+        PROPOSAL_1.parse().unwrap(),
+        PROPOSAL_2.parse().unwrap(),
+    ])
+    .hash();
+    let proposal = proposals.get(&tree).unwrap();
+
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposal.votes.len(), 1);
+    assert_eq!(
+        proposal.votes[0].address,
+        address!("E5eCC5DaBe88ed1FB20F0b5C6C44BFa3A137bdff")
+    );
+    assert_eq!(proposal.votes[0].points, U256::from(120010000));
+    assert_eq!(
+        proposal.root,
+        MerkleTree::from_leaves(proposal.votes.iter().map(B256::from)).root()
+    );
+}
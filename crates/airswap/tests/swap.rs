@@ -0,0 +1,25 @@
+use std::env;
+
+use airswap::{get_swap_history_with_timestamps, Config};
+use alloy::providers::ProviderBuilder;
+use dotenv::dotenv;
+
+#[tokio::test]
+async fn test_swap_history() {
+    dotenv().ok();
+
+    let eth_rpc = env::var("ETH_RPC_URL").unwrap();
+    let provider = ProviderBuilder::new().on_http(eth_rpc.parse().unwrap());
+    let config = Config::mainnet_v4();
+
+    let records =
+        get_swap_history_with_timestamps(&provider, config.swap_address, 19000000, Some(19010000))
+            .await
+            .unwrap();
+
+    for record in records {
+        assert!(record.block_number >= 19000000 && record.block_number <= 19010000);
+        assert!(record.timestamp.is_some());
+        assert_eq!(record.address, config.swap_address);
+    }
+}